@@ -12,6 +12,7 @@ impl Plugin for AppPlugin {
 #[derive(States, Default, Debug, PartialEq, Eq, Clone, Hash, Reflect)]
 pub enum AppState {
     #[default]
+    Loading,
     Menu,
     InGame,
 }
@@ -1,10 +1,18 @@
 use bevy::{math::vec2, prelude::*, render::camera::CameraUpdateSystem};
 
+use crate::{course::CurrentCourse, course_data::Courses};
+
 pub struct BackgroundPlugin;
 
 impl Plugin for BackgroundPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(PostStartup, setup.after(CameraUpdateSystem));
+
+        app.add_systems(
+            Update,
+            apply_course_theme
+                .run_if(resource_changed::<CurrentCourse>.or(resource_changed::<Courses>)),
+        );
     }
 }
 
@@ -36,3 +44,17 @@ fn setup(
         Transform::from_xyz(0.0, 0.0, -1000.0),
     ));
 }
+
+fn apply_course_theme(
+    courses: Res<Courses>,
+    current_course: Res<CurrentCourse>,
+    mut background_q: Query<&mut Sprite, With<Background>>,
+) {
+    let Some(course) = courses.get(**current_course) else {
+        return;
+    };
+
+    for mut sprite in &mut background_q {
+        sprite.image = course.theme.background.clone();
+    }
+}
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 
 use crate::{
     cam::LevelBounds,
+    checkpoint::ReachedCheckpoint,
     level::{Floor, LevelState, Tee},
 };
 
@@ -34,7 +35,23 @@ impl Plugin for BallPlugin {
 #[derive(Event)]
 pub struct BallResetEvent;
 
-fn oob_check(
+/// How hard the ball is pushing against whatever it's touching, less its own weight so just resting on
+/// something doesn't count
+pub fn ball_contact_force(
+    contacts: &Contacts,
+    ball_mass: &ComputedMass,
+    gravity: &Gravity,
+    time: &Time<Substeps>,
+) -> f32 {
+    // Levels can turn gravity, so only how strong it is matters here
+    let weight = -ball_mass.value() * gravity.0.length();
+
+    let normal_force = contacts.total_normal_impulse / time.delta_secs();
+
+    normal_force + weight
+}
+
+pub fn oob_check(
     ball_q: Query<&Position, With<Ball>>,
     level_bounds: Res<LevelBounds>,
     mut events: EventWriter<BallResetEvent>,
@@ -62,6 +79,7 @@ fn reset_ball(
         With<Ball>,
     >,
     current_level_q: Query<(&Position, &Tee), (With<Floor>, Without<Ball>)>,
+    reached_checkpoint: Res<ReachedCheckpoint>,
 ) {
     if events.is_empty() {
         return;
@@ -73,7 +91,7 @@ fn reset_ball(
 
     let (level_pos, level_tee) = current_level_q.single();
 
-    pos.0 = level_pos.0 + level_tee.0;
+    pos.0 = level_pos.0 + reached_checkpoint.unwrap_or(level_tee.0);
     *rot = Rotation::IDENTITY;
     vel.0 = Vec2::ZERO;
     roll.0 = 0.0;
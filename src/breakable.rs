@@ -0,0 +1,184 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    ball::{ball_contact_force, Ball, BallResetEvent},
+    checkpoint::no_checkpoint_reached,
+    direction::Direction,
+    level::LevelElement,
+    level_data::{polygon_collider, triangles, LevelBreakable},
+};
+
+pub struct BreakablePlugin;
+
+impl Plugin for BreakablePlugin {
+    fn build(&self, app: &mut App) {
+        // Alongside the bounce sounds, which go by how hard the ball hit something in the same way
+        app.add_systems(PostProcessCollisions, break_walls);
+
+        app.add_systems(
+            Update,
+            (
+                // Walls broken on the way to a checkpoint stay broken, like stars stay collected
+                restore_walls.run_if(on_event::<BallResetEvent>.and(no_checkpoint_reached)),
+                tidy_debris,
+            ),
+        );
+    }
+}
+
+/// Roughly a firm chip shot into the wall, the same scale the bounce sounds use
+const DEFAULT_WALL_STRENGTH: f32 = 60_000_000.0;
+/// How much of the ball's speed the pieces of a broken wall fly off with
+const DEBRIS_SPEED_FACTOR: f32 = 0.5;
+const DEBRIS_LIFETIME: f32 = 3.0;
+
+/// A wall as it's written in a level file
+#[derive(Deserialize)]
+pub struct RawBreakable {
+    /// Outline of the wall, starting from the same origin as the level's outline
+    pub directions: Vec<Direction>,
+    /// How hard the ball has to hit the wall to break it
+    #[serde(default = "default_wall_strength")]
+    pub strength: f32,
+}
+
+fn default_wall_strength() -> f32 {
+    DEFAULT_WALL_STRENGTH
+}
+
+#[derive(Component)]
+pub struct BreakableWall {
+    pub points: Vec<Vec2>,
+    pub strength: f32,
+}
+
+/// A wall that's been broken and is waiting for the ball to go back to the tee
+#[derive(Component)]
+pub struct Broken;
+
+/// A piece of a broken wall, it disappears after a while
+#[derive(Component)]
+struct Debris(Timer);
+
+/// Spawned as a child of the floor so it's part of the level's static body until it breaks
+pub fn breakable_bundle(breakable: &LevelBreakable) -> impl Bundle {
+    (
+        Name::new("Breakable Wall"),
+        LevelElement,
+        BreakableWall {
+            points: breakable.points.clone(),
+            strength: breakable.strength,
+        },
+        Mesh2d(breakable.mesh.clone()),
+        MeshMaterial2d(breakable.material.clone()),
+        polygon_collider(&breakable.points).unwrap_or_default(),
+        Transform::default(),
+    )
+}
+
+fn break_walls(
+    mut commands: Commands,
+    ball_q: Query<(Entity, &ComputedMass, &Position, &LinearVelocity), With<Ball>>,
+    wall_q: Query<
+        (
+            &BreakableWall,
+            &MeshMaterial2d<ColorMaterial>,
+            &GlobalTransform,
+        ),
+        Without<Broken>,
+    >,
+    mut collisions: ResMut<Collisions>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    gravity: Res<Gravity>,
+    time: Res<Time<Substeps>>,
+) {
+    let Ok((ball_entity, ball_mass, ball_pos, ball_vel)) = ball_q.get_single() else {
+        return;
+    };
+
+    let mut broken_walls = Vec::new();
+
+    for collision in collisions.collisions_with_entity(ball_entity) {
+        let wall_entity = if collision.entity1 == ball_entity {
+            collision.entity2
+        } else {
+            collision.entity1
+        };
+
+        let Ok((wall, material, transform)) = wall_q.get(wall_entity) else {
+            continue;
+        };
+
+        if ball_contact_force(collision, ball_mass, &gravity, &time) < wall.strength {
+            continue;
+        }
+
+        commands
+            .entity(wall_entity)
+            .insert((Broken, Visibility::Hidden))
+            .remove::<Collider>();
+        broken_walls.push(wall_entity);
+
+        let offset = transform.translation().truncate();
+
+        for [a, b, c] in triangles(&wall.points) {
+            let centre = (a + b + c) / 3.0;
+            let piece = Triangle2d::new(a - centre, b - centre, c - centre);
+            // Pieces fly away from where the ball hit
+            let away = (offset + centre - ball_pos.0).normalize_or_zero();
+
+            commands.spawn((
+                Name::new("Debris"),
+                LevelElement,
+                Debris(Timer::from_seconds(DEBRIS_LIFETIME, TimerMode::Once)),
+                Mesh2d(meshes.add(piece)),
+                MeshMaterial2d(material.0.clone()),
+                Collider::triangle(piece.vertices[0], piece.vertices[1], piece.vertices[2]),
+                RigidBody::Dynamic,
+                Position(offset + centre),
+                LinearVelocity(away * ball_vel.length() * DEBRIS_SPEED_FACTOR),
+                Transform::from_translation((offset + centre).extend(0.0)),
+            ));
+        }
+    }
+
+    // The ball goes straight through a wall it breaks instead of bouncing off it first
+    collisions.retain(|contacts| {
+        let is_ball = contacts.entity1 == ball_entity || contacts.entity2 == ball_entity;
+        let is_broken_wall =
+            broken_walls.contains(&contacts.entity1) || broken_walls.contains(&contacts.entity2);
+        !(is_ball && is_broken_wall)
+    });
+}
+
+/// Put every broken wall back and clear away the pieces, whenever the ball goes back to the tee
+pub fn restore_walls(
+    mut commands: Commands,
+    wall_q: Query<(Entity, &BreakableWall), With<Broken>>,
+    debris_q: Query<Entity, With<Debris>>,
+) {
+    for (entity, wall) in &wall_q {
+        commands.entity(entity).remove::<Broken>().insert((
+            polygon_collider(&wall.points).unwrap_or_default(),
+            Visibility::Inherited,
+        ));
+    }
+
+    for entity in &debris_q {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn tidy_debris(
+    mut commands: Commands,
+    mut debris_q: Query<(Entity, &mut Debris)>,
+    time: Res<Time>,
+) {
+    for (entity, mut debris) in &mut debris_q {
+        if debris.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
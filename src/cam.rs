@@ -1,6 +1,6 @@
 use bevy::{prelude::*, render::primitives::Aabb, window::PrimaryWindow};
 
-use crate::level::Floor;
+use crate::{checkpoint::Checkpoint, level::Floor};
 
 pub struct CamPlugin;
 
@@ -17,6 +17,10 @@ fn spawn_cam(mut commands: Commands) {
     commands.spawn((Camera2d, IsDefaultUiCamera));
 }
 
+/// The window's size when the game starts
+pub const WINDOW_SIZE: Vec2 = Vec2::new(720.0, 1216.0);
+
+/// What the camera shows, the ball leaving it is out of bounds
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct LevelBounds(pub Rect);
 
@@ -36,6 +40,7 @@ fn update_projection_area(
 pub fn on_level_resize_zoom(
     trigger: Trigger<OnAdd, Aabb>,
     mut level_q: Query<&Aabb, With<Floor>>,
+    checkpoint_q: Query<&Checkpoint>,
     mut camera_q: Query<(&mut OrthographicProjection, &mut Transform)>,
     window_q: Query<&Window, With<PrimaryWindow>>,
 ) {
@@ -48,11 +53,22 @@ pub fn on_level_resize_zoom(
     let level_width = level_aabb.half_extents.x * 2.0;
     let level_height = level_aabb.half_extents.y * 2.0;
 
-    let factor = level_width / projection.area.width();
+    // Checkpoint gates can reach above the terrain, zoom out far enough to see where the ball will go back to
+    let level_bottom = level_aabb.center.y - level_aabb.half_extents.y;
+    let gate_height = checkpoint_q
+        .iter()
+        .map(|checkpoint| checkpoint.position.y + checkpoint.height - level_bottom)
+        .fold(0.0, f32::max);
 
-    projection.scale *= factor;
+    projection.scale *= zoom_to_fit(projection.area, level_width, gate_height);
 
     let translation = 0.5 * (height * projection.scale - level_height).abs();
 
     transform.translation = Vec3::Y * translation;
 }
+
+/// How much further out a camera showing `area` has to zoom to fit the whole width of a level, and checkpoint gates
+/// `gate_height` up from its bottom
+pub fn zoom_to_fit(area: Rect, level_width: f32, gate_height: f32) -> f32 {
+    (level_width / area.width()).max(gate_height / area.height())
+}
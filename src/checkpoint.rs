@@ -0,0 +1,176 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{EMERALD_400, SLATE_300},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    ball::{Ball, BallStoppedEvent},
+    level::{Floor, LevelElement, LevelState, Tee},
+};
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReachedCheckpoint>();
+
+        app.add_systems(
+            Update,
+            pass_through_checkpoints.run_if(in_state(LevelState::InPlay)),
+        );
+        // Alongside water remembering where the ball stopped
+        app.add_systems(Last, reach_checkpoints.run_if(on_event::<BallStoppedEvent>));
+    }
+}
+
+const DEFAULT_GATE_HEIGHT: f32 = 120.0;
+const GATE_WIDTH: f32 = 4.0;
+
+pub const CHECKPOINT_COLOUR: Srgba = SLATE_300;
+pub const REACHED_CHECKPOINT_COLOUR: Srgba = EMERALD_400;
+
+/// A checkpoint as it's written in a level file
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RawCheckpoint {
+    /// Where the ball restarts, positioned the same way as the level's `tee`
+    pub position: Vec2,
+    /// How far up from `position` the ball counts as going through
+    #[serde(default = "default_gate_height")]
+    pub height: f32,
+}
+
+fn default_gate_height() -> f32 {
+    DEFAULT_GATE_HEIGHT
+}
+
+pub fn gate_mesh(height: f32) -> Mesh {
+    Rectangle::new(GATE_WIDTH, height).into()
+}
+
+pub struct LevelCheckpoint {
+    pub position: Vec2,
+    pub height: f32,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+    pub reached_material: Handle<ColorMaterial>,
+}
+
+/// Where out of bounds resets put the ball instead of the tee, in the floor's space. Cleared whenever a level
+/// is loaded.
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct ReachedCheckpoint(pub Option<Vec2>);
+
+/// Run condition for anything that should only happen when the ball goes all the way back to the tee
+pub fn no_checkpoint_reached(reached_checkpoint: Res<ReachedCheckpoint>) -> bool {
+    reached_checkpoint.is_none()
+}
+
+#[derive(Component)]
+pub struct Checkpoint {
+    pub position: Vec2,
+    pub height: f32,
+    material: Handle<ColorMaterial>,
+    reached_material: Handle<ColorMaterial>,
+}
+
+/// The ball has gone through this checkpoint's gate since it last stopped
+#[derive(Component)]
+struct Passed;
+
+/// Spawned as a child of the floor, a gate standing up from where the ball restarts
+pub fn checkpoint_bundle(checkpoint: &LevelCheckpoint, index: usize) -> impl Bundle {
+    (
+        Name::new(format!("Checkpoint {index}")),
+        LevelElement,
+        Checkpoint {
+            position: checkpoint.position,
+            height: checkpoint.height,
+            material: checkpoint.material.clone(),
+            reached_material: checkpoint.reached_material.clone(),
+        },
+        Mesh2d(checkpoint.mesh.clone()),
+        MeshMaterial2d(checkpoint.material.clone()),
+        Collider::rectangle(GATE_WIDTH, checkpoint.height),
+        Sensor,
+        // Behind the terrain's overlay, like zones
+        Transform::from_translation(
+            (checkpoint.position + Vec2::Y * checkpoint.height / 2.0).extend(0.005),
+        ),
+    )
+}
+
+fn pass_through_checkpoints(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionStarted>,
+    ball_q: Query<Entity, With<Ball>>,
+    checkpoint_q: Query<(), With<Checkpoint>>,
+) {
+    let Ok(ball_entity) = ball_q.get_single() else {
+        return;
+    };
+
+    for CollisionStarted(entity1, entity2) in collision_events.read() {
+        let other_entity = if *entity1 == ball_entity {
+            *entity2
+        } else if *entity2 == ball_entity {
+            *entity1
+        } else {
+            continue;
+        };
+
+        if checkpoint_q.contains(other_entity) {
+            commands.entity(other_entity).insert(Passed);
+        }
+    }
+}
+
+/// A checkpoint is reached when the ball stops on the far side of it from the tee, having gone through its gate.
+/// The ball never goes back to an earlier checkpoint than one it's already reached.
+fn reach_checkpoints(
+    mut commands: Commands,
+    ball_q: Query<&Position, With<Ball>>,
+    floor_q: Query<(&Position, &Tee), (With<Floor>, Without<Ball>)>,
+    mut checkpoint_q: Query<(
+        Entity,
+        &Checkpoint,
+        &mut MeshMaterial2d<ColorMaterial>,
+        Has<Passed>,
+    )>,
+    mut reached_checkpoint: ResMut<ReachedCheckpoint>,
+) {
+    let (Ok(ball_pos), Ok((floor_pos, tee))) = (ball_q.get_single(), floor_q.get_single()) else {
+        return;
+    };
+
+    let ball_pos = ball_pos.0 - floor_pos.0;
+
+    let is_past = |position: Vec2| {
+        let ball_side = (ball_pos.x - position.x).signum();
+        let tee_side = (tee.x - position.x).signum();
+        ball_side != tee_side
+    };
+    let progress = |position: Vec2| (position.x - tee.x).abs();
+
+    let furthest = checkpoint_q
+        .iter()
+        .filter(|(_, checkpoint, _, passed)| *passed && is_past(checkpoint.position))
+        .map(|(_, checkpoint, _, _)| checkpoint.position)
+        .chain(**reached_checkpoint)
+        .max_by(|a, b| progress(*a).total_cmp(&progress(*b)));
+
+    **reached_checkpoint = furthest;
+
+    for (entity, checkpoint, mut material, passed) in &mut checkpoint_q {
+        if passed {
+            commands.entity(entity).remove::<Passed>();
+        }
+
+        material.0 = if Some(checkpoint.position) == furthest {
+            checkpoint.reached_material.clone()
+        } else {
+            checkpoint.material.clone()
+        };
+    }
+}
@@ -1,8 +1,10 @@
 use bevy::color::palettes::css::BLACK;
 use bevy::prelude::*;
 
-use crate::level::load_level;
-use crate::{app::AppState, level::LevelState, level_data::Levels};
+use crate::level::{load_level, CupSunkEvent};
+use crate::level_data::CurrentLevel;
+use crate::star::HoleStars;
+use crate::{app::AppState, course_data::Courses, level::LevelState};
 
 #[derive(States, Default, Debug, PartialEq, Eq, Clone, Hash, Reflect)]
 pub enum CourseState {
@@ -20,7 +22,13 @@ impl Plugin for CoursePlugin {
         app.init_state::<CourseState>();
         app.enable_state_scoped_entities::<CourseState>();
 
-        app.init_resource::<NextLevelIndex>();
+        app.init_resource::<CurrentCourse>()
+            .init_resource::<NextLevelIndex>()
+            .init_resource::<CoursePoints>()
+            .init_resource::<CourseStars>();
+
+        app.add_systems(Update, add_cup_points.run_if(on_event::<CupSunkEvent>));
+        app.add_systems(OnEnter(LevelState::Won), add_hole_stars);
 
         app.add_systems(OnExit(LevelState::Won), advance_level_or_win_course);
         app.add_systems(
@@ -41,27 +49,26 @@ impl Plugin for CoursePlugin {
             },
         );
 
+        // The course picked in the menu might not be the one shown behind it
         app.add_systems(
             OnEnter(AppState::InGame),
-            (|mut next_course_state: ResMut<NextState<CourseState>>| {
-                next_course_state.set(CourseState::Playing);
-            },)
-                .chain(),
-        );
-
-        app.add_systems(
-            OnEnter(AppState::Menu),
             (
-                |mut next_level_index: ResMut<NextLevelIndex>| {
-                    **next_level_index = 0;
-                },
+                reset_next_level_index,
+                reset_course_points,
+                reset_course_stars,
                 load_level,
+                |mut next_course_state: ResMut<NextState<CourseState>>| {
+                    next_course_state.set(CourseState::Playing);
+                },
             )
                 .chain(),
         );
 
         // Show level behind main menu
-        app.add_systems(PostStartup, load_level);
+        app.add_systems(
+            OnEnter(AppState::Menu),
+            (reset_next_level_index, load_level).chain(),
+        );
 
         app.add_systems(OnEnter(CourseState::Won), display_course_over_screen)
             .add_systems(OnEnter(CourseState::Failed), display_course_over_screen)
@@ -75,15 +82,66 @@ impl Plugin for CoursePlugin {
     }
 }
 
+/// Index into `Courses` of the course being played
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct CurrentCourse(usize);
+
 #[derive(Resource, Default, Deref, DerefMut, Debug)]
 pub struct NextLevelIndex(usize);
 
+/// Points from every cup sunk so far on this course
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct CoursePoints(u32);
+
+/// Stars collected on every hole finished so far on this course, and how many those holes had between them
+#[derive(Resource, Default, Debug)]
+pub struct CourseStars {
+    pub collected: u32,
+    pub total: u32,
+}
+
+fn reset_next_level_index(mut next_level_index: ResMut<NextLevelIndex>) {
+    **next_level_index = 0;
+}
+
+fn reset_course_points(mut course_points: ResMut<CoursePoints>) {
+    **course_points = 0;
+}
+
+fn reset_course_stars(mut course_stars: ResMut<CourseStars>) {
+    *course_stars = CourseStars::default();
+}
+
+fn add_hole_stars(
+    current_level: CurrentLevel,
+    hole_stars: Res<HoleStars>,
+    mut course_stars: ResMut<CourseStars>,
+) {
+    let Some(level) = current_level.get() else {
+        return;
+    };
+
+    course_stars.collected += **hole_stars;
+    course_stars.total += level.stars.len() as u32;
+}
+
+fn add_cup_points(mut events: EventReader<CupSunkEvent>, mut course_points: ResMut<CoursePoints>) {
+    for event in events.read() {
+        **course_points += event.points;
+    }
+}
+
 fn advance_level_or_win_course(
-    levels: Res<Levels>,
+    courses: Res<Courses>,
+    current_course: Res<CurrentCourse>,
     mut next_level_index: ResMut<NextLevelIndex>,
     mut next_course_state: ResMut<NextState<CourseState>>,
 ) {
-    if **next_level_index + 1 >= levels.0.len() {
+    let level_count = courses
+        .get(**current_course)
+        .map_or(0, |course| course.levels.len());
+
+    if **next_level_index + 1 >= level_count {
         next_course_state.set(CourseState::Won);
         return;
     }
@@ -96,7 +154,12 @@ fn advance_level_or_win_course(
 #[derive(Component)]
 struct CourseOverScreen;
 
-fn display_course_over_screen(mut commands: Commands, course_state: Res<State<CourseState>>) {
+fn display_course_over_screen(
+    mut commands: Commands,
+    course_state: Res<State<CourseState>>,
+    course_points: Res<CoursePoints>,
+    course_stars: Res<CourseStars>,
+) {
     let text = match course_state.get() {
         CourseState::Won => "WINNER!",
         CourseState::Failed => "GAME OVER",
@@ -123,6 +186,22 @@ fn display_course_over_screen(mut commands: Commands, course_state: Res<State<Co
                 TextFont::from_font_size(120.0),
                 TextColor::WHITE,
             ));
+            builder.spawn((
+                Text::new(format!("{} POINTS", **course_points)),
+                TextFont::from_font_size(48.0),
+                TextColor::WHITE,
+            ));
+
+            if course_stars.total > 0 {
+                builder.spawn((
+                    Text::new(format!(
+                        "{} / {} STARS",
+                        course_stars.collected, course_stars.total
+                    )),
+                    TextFont::from_font_size(48.0),
+                    TextColor::WHITE,
+                ));
+            }
         });
 }
 
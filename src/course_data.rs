@@ -0,0 +1,237 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext, LoadState},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{app::AppState, level_data::Level};
+
+pub struct CourseDataPlugin;
+
+impl Plugin for CourseDataPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CourseManifest>()
+            .init_asset_loader::<CourseManifestLoader>()
+            .init_resource::<Courses>();
+
+        app.add_systems(PreStartup, load_course_manifest);
+
+        app.add_systems(
+            Update,
+            finish_loading_courses.run_if(in_state(AppState::Loading)),
+        );
+    }
+}
+
+/// Lists every course, used instead of scanning folders so it also works on the web
+const COURSE_MANIFEST: &str = "levels/main.courses.json";
+
+pub const DEFAULT_BACKGROUND: &str = "images/pixel.png";
+pub const DEFAULT_MUSIC: &str = "songs/otherside.ogg";
+
+/// Every course that has at least one level that loaded, in the order they're listed in the manifest
+#[derive(Resource, Default, Deref)]
+pub struct Courses(pub Vec<Course>);
+
+#[derive(Asset, TypePath)]
+pub struct CourseManifest {
+    pub courses: Vec<Course>,
+}
+
+#[derive(Clone)]
+pub struct Course {
+    pub name: String,
+    /// In the order they're played
+    pub levels: Vec<Handle<Level>>,
+    pub theme: Theme,
+    pub music: Handle<AudioSource>,
+}
+
+#[derive(Clone)]
+pub struct Theme {
+    pub background: Handle<Image>,
+}
+
+/// The manifest as it's written in a `.courses.json` file, all paths are from the assets folder
+#[derive(Deserialize)]
+struct RawCourseManifest {
+    courses: Vec<RawCourse>,
+}
+
+#[derive(Deserialize)]
+struct RawCourse {
+    name: String,
+    levels: Vec<String>,
+    #[serde(default)]
+    theme: RawTheme,
+    #[serde(default = "default_music")]
+    music: String,
+}
+
+#[derive(Deserialize)]
+struct RawTheme {
+    #[serde(default = "default_background")]
+    background: String,
+}
+
+impl Default for RawTheme {
+    fn default() -> Self {
+        Self {
+            background: default_background(),
+        }
+    }
+}
+
+fn default_background() -> String {
+    DEFAULT_BACKGROUND.to_string()
+}
+
+fn default_music() -> String {
+    DEFAULT_MUSIC.to_string()
+}
+
+#[derive(Default)]
+struct CourseManifestLoader;
+
+#[derive(Debug)]
+pub enum CourseManifestLoaderError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for CourseManifestLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CourseManifestLoaderError::Io(err) => {
+                write!(f, "Could not read course manifest: {err}")
+            }
+            CourseManifestLoaderError::Json(err) => {
+                write!(f, "Could not parse course manifest: {err}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CourseManifestLoaderError {}
+
+impl From<std::io::Error> for CourseManifestLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        CourseManifestLoaderError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CourseManifestLoaderError {
+    fn from(err: serde_json::Error) -> Self {
+        CourseManifestLoaderError::Json(err)
+    }
+}
+
+impl AssetLoader for CourseManifestLoader {
+    type Asset = CourseManifest;
+    type Settings = ();
+    type Error = CourseManifestLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<CourseManifest, CourseManifestLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let RawCourseManifest { courses } = serde_json::from_slice(&bytes)?;
+
+        let courses = courses
+            .into_iter()
+            .map(|course| Course {
+                name: course.name,
+                levels: course
+                    .levels
+                    .into_iter()
+                    .map(|path| load_context.load(path))
+                    .collect(),
+                theme: Theme {
+                    background: load_context.load(course.theme.background),
+                },
+                music: load_context.load(course.music),
+            })
+            .collect();
+
+        Ok(CourseManifest { courses })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["courses.json"]
+    }
+}
+
+#[derive(Resource)]
+struct CourseManifestHandle(Handle<CourseManifest>);
+
+fn load_course_manifest(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CourseManifestHandle(asset_server.load(COURSE_MANIFEST)));
+}
+
+fn finish_loading_courses(
+    asset_server: Res<AssetServer>,
+    manifest_handle: Res<CourseManifestHandle>,
+    manifests: Res<Assets<CourseManifest>>,
+    mut courses: ResMut<Courses>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if let LoadState::Failed(err) = asset_server.load_state(&manifest_handle.0) {
+        error!("No courses to play: {err}");
+        next_app_state.set(AppState::Menu);
+        return;
+    }
+
+    let Some(manifest) = manifests.get(&manifest_handle.0) else {
+        return;
+    };
+
+    // Wait until every level has either loaded or failed
+    let is_done_loading = |handle: &Handle<Level>| {
+        asset_server.is_loaded(handle) || asset_server.load_state(handle).is_failed()
+    };
+
+    if !manifest
+        .courses
+        .iter()
+        .flat_map(|course| &course.levels)
+        .all(is_done_loading)
+    {
+        return;
+    }
+
+    courses.0 = manifest
+        .courses
+        .iter()
+        .filter_map(|course| {
+            let levels: Vec<Handle<Level>> = course
+                .levels
+                .iter()
+                .filter(|handle| {
+                    if let LoadState::Failed(err) = asset_server.load_state(*handle) {
+                        warn!("Skipping level: {err}");
+                        return false;
+                    }
+                    true
+                })
+                .cloned()
+                .collect();
+
+            if levels.is_empty() {
+                warn!("Skipping course {}: none of its levels loaded", course.name);
+                return None;
+            }
+
+            Some(Course {
+                levels,
+                ..course.clone()
+            })
+        })
+        .collect();
+
+    next_app_state.set(AppState::Menu);
+}
@@ -0,0 +1,149 @@
+use avian2d::prelude::*;
+use bevy::{math::vec2, prelude::*};
+use serde::Deserialize;
+
+use crate::{level::BALL_RADIUS, level_validation::LevelError};
+
+const DEFAULT_CUP_DEPTH: f32 = 50.0;
+const DEFAULT_CUP_POINTS: u32 = 1;
+
+/// How far the cup's width can be from the notch's before they're considered different
+const NOTCH_TOLERANCE: f32 = 0.01;
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CupShape {
+    /// Fills the cup up to its depth
+    #[default]
+    Rectangle,
+    /// A circle resting on the bottom of the cup
+    Circle,
+    /// A thin strip along the bottom, the ball has to drop all the way in
+    Bottom,
+}
+
+/// A cup as it's written in a level file, the width defaults to the width of the notch it sits in
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RawCup {
+    pub width: Option<f32>,
+    #[serde(default = "default_cup_depth")]
+    pub depth: f32,
+    #[serde(default)]
+    pub shape: CupShape,
+    /// What sinking the ball in this cup is worth
+    #[serde(default = "default_cup_points")]
+    pub points: u32,
+}
+
+fn default_cup_depth() -> f32 {
+    DEFAULT_CUP_DEPTH
+}
+
+fn default_cup_points() -> u32 {
+    DEFAULT_CUP_POINTS
+}
+
+/// Another cup the hole can be finished in, sitting in the notch at `goal_index`
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RawAlternateCup {
+    pub goal_index: usize,
+    #[serde(flatten)]
+    pub cup: RawCup,
+}
+
+impl Default for RawCup {
+    fn default() -> Self {
+        Self {
+            width: None,
+            depth: DEFAULT_CUP_DEPTH,
+            shape: CupShape::default(),
+            points: DEFAULT_CUP_POINTS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cup {
+    pub width: f32,
+    pub depth: f32,
+    pub shape: CupShape,
+    pub points: u32,
+}
+
+/// A cup in place in its level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelCup {
+    pub bottom_left: Vec2,
+    pub cup: Cup,
+}
+
+impl LevelCup {
+    /// Where the centre of the cup's collider goes
+    pub fn sensor_position(&self) -> Vec2 {
+        self.bottom_left + self.cup.sensor_offset()
+    }
+}
+
+impl Cup {
+    /// Make sure the cup fits the notch whose bottom left corner is `points[goal_index]`
+    pub fn fit_to_notch(
+        raw: RawCup,
+        points: &[Vec2],
+        goal_index: usize,
+    ) -> Result<Cup, LevelError> {
+        let (Some(&top_left), Some(&bottom_left), Some(&bottom_right), Some(&top_right)) = (
+            goal_index.checked_sub(1).and_then(|i| points.get(i)),
+            points.get(goal_index),
+            points.get(goal_index + 1),
+            points.get(goal_index + 2),
+        ) else {
+            return Err(LevelError::NoNotch);
+        };
+
+        let notch_width = bottom_right.x - bottom_left.x;
+        let notch_depth = top_left.y.min(top_right.y) - bottom_left.y;
+
+        if notch_width <= 0.0 || notch_depth <= 0.0 {
+            return Err(LevelError::NoNotch);
+        }
+
+        let width = raw.width.unwrap_or(notch_width);
+
+        if (width - notch_width).abs() > NOTCH_TOLERANCE {
+            return Err(LevelError::CupWidthMismatch { width, notch_width });
+        }
+
+        if raw.depth <= 0.0 || raw.depth > notch_depth {
+            return Err(LevelError::CupTooDeep {
+                depth: raw.depth,
+                notch_depth,
+            });
+        }
+
+        Ok(Cup {
+            width,
+            depth: raw.depth,
+            shape: raw.shape,
+            points: raw.points,
+        })
+    }
+
+    pub fn collider(&self) -> Collider {
+        match self.shape {
+            CupShape::Rectangle => Collider::rectangle(self.width, self.depth),
+            CupShape::Circle => Collider::circle(self.width.min(self.depth) / 2.0),
+            CupShape::Bottom => Collider::rectangle(self.width, BALL_RADIUS),
+        }
+    }
+
+    /// Where the centre of the collider is relative to the bottom left of the cup
+    pub fn sensor_offset(&self) -> Vec2 {
+        match self.shape {
+            CupShape::Rectangle => vec2(self.width, self.depth) / 2.0,
+            CupShape::Circle => {
+                Vec2::splat(self.width.min(self.depth) / 2.0).with_x(self.width / 2.0)
+            }
+            CupShape::Bottom => vec2(self.width, BALL_RADIUS) / 2.0,
+        }
+    }
+}
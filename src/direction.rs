@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// How many straight segments a curve is broken into if the level doesn't say otherwise
+pub const DEFAULT_CURVE_RESOLUTION: u32 = 16;
+
+/// One step of a level outline, everything is relative to where the previous step finished.
+/// Written as `[x, y]` for a straight line, or as e.g. `{ "arc": { "to": [x, y], "angle": 90.0 } }` for a curve.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum Direction {
+    Line(Vec2),
+    Curve(Curve),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Curve {
+    Quadratic {
+        control: Vec2,
+        to: Vec2,
+    },
+    Cubic {
+        control_1: Vec2,
+        control_2: Vec2,
+        to: Vec2,
+    },
+    /// Part of a circle sweeping `angle` degrees, anticlockwise when positive.
+    /// Going right, a positive angle makes a bowl and a negative one makes a hill.
+    Arc {
+        to: Vec2,
+        angle: f32,
+    },
+}
+
+impl From<Vec2> for Direction {
+    fn from(vec: Vec2) -> Self {
+        Direction::Line(vec)
+    }
+}
+
+impl Direction {
+    /// Where this step ends up relative to where it started
+    pub fn offset(&self) -> Vec2 {
+        match *self {
+            Direction::Line(to)
+            | Direction::Curve(Curve::Quadratic { to, .. })
+            | Direction::Curve(Curve::Cubic { to, .. })
+            | Direction::Curve(Curve::Arc { to, .. }) => to,
+        }
+    }
+
+    /// The points this step visits after `start`, finishing on its end point
+    fn tessellate(&self, start: Vec2, resolution: u32) -> Vec<Vec2> {
+        let curve = match *self {
+            Direction::Line(to) => return vec![start + to],
+            Direction::Curve(curve) => curve,
+        };
+
+        let resolution = resolution.max(1);
+        let ts = (1..=resolution).map(|i| i as f32 / resolution as f32);
+
+        match curve {
+            Curve::Quadratic { control, to } => {
+                let (p1, p2) = (start + control, start + to);
+
+                ts.map(|t| {
+                    let u = 1.0 - t;
+                    u * u * start + 2.0 * u * t * p1 + t * t * p2
+                })
+                .collect()
+            }
+            Curve::Cubic {
+                control_1,
+                control_2,
+                to,
+            } => {
+                let (p1, p2, p3) = (start + control_1, start + control_2, start + to);
+
+                ts.map(|t| {
+                    let u = 1.0 - t;
+                    u * u * u * start + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+                })
+                .collect()
+            }
+            Curve::Arc { to, angle } => {
+                let sweep = angle.to_radians();
+
+                if sweep.abs() < f32::EPSILON || to == Vec2::ZERO {
+                    return vec![start + to];
+                }
+
+                // The centre sits on the chord's perpendicular bisector
+                let centre = start + to / 2.0 + to.perp() / 2.0 / (sweep / 2.0).tan();
+                let radius = start.distance(centre);
+                let start_angle = (start - centre).to_angle();
+
+                let mut points: Vec<Vec2> = ts
+                    .map(|t| centre + Vec2::from_angle(start_angle + sweep * t) * radius)
+                    .collect();
+
+                // Land exactly on the end so following steps don't drift
+                if let Some(last) = points.last_mut() {
+                    *last = start + to;
+                }
+
+                points
+            }
+        }
+    }
+}
+
+/// Turn a list of directions of how to go from one coordinate to the next into a list of those coordinates,
+/// with curves broken up into `resolution` straight segments.
+/// Also returns, for each direction, the index of the coordinate it finishes on.
+pub fn directions_to_points(directions: &[Direction], resolution: u32) -> (Vec<Vec2>, Vec<usize>) {
+    let mut points = Vec::new();
+    let mut ends = Vec::with_capacity(directions.len());
+
+    let mut running = Vec2::ZERO;
+
+    for direction in directions {
+        points.extend(direction.tessellate(running, resolution));
+        running += direction.offset();
+        ends.push(points.len() - 1);
+    }
+
+    (points, ends)
+}
@@ -0,0 +1,255 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{CYAN_400, PINK_500, YELLOW_400},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    ball::Ball,
+    level::{LevelElement, BALL_RADIUS},
+    physics::PhysicsState,
+};
+
+pub struct GadgetPlugin;
+
+impl Plugin for GadgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GadgetTriggeredEvent>();
+
+        app.add_systems(Update, kick_ball_off_gadgets);
+        // Alongside obstacles so the push is the same whatever the frame rate
+        app.add_systems(
+            FixedUpdate,
+            push_ball_along_boost_pads.run_if(in_state(PhysicsState::Running)),
+        );
+    }
+}
+
+const DEFAULT_BUMPER_RADIUS: f32 = 20.0;
+const DEFAULT_BUMPER_KICK: f32 = 300.0;
+const DEFAULT_PAD_WIDTH: f32 = 50.0;
+const DEFAULT_LAUNCH_SPEED: f32 = 900.0;
+const DEFAULT_BOOST_ACCELERATION: f32 = 600.0;
+
+const SPRING_THICKNESS: f32 = 8.0;
+/// Boost pads reach this far above the ground so the ball counts as on them while it rolls across
+const BOOST_HEIGHT: f32 = BALL_RADIUS * 2.0;
+
+/// A level element that does something to the ball when it touches it
+#[derive(Component, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Gadget {
+    /// Extra bouncy and kicks the ball away from its centre
+    Bumper {
+        #[serde(default = "default_bumper_radius")]
+        radius: f32,
+        /// Speed added to the ball when it hits
+        #[serde(default = "default_bumper_kick")]
+        kick: f32,
+    },
+    /// Sits on the ground and launches the ball straight out of its top
+    Spring {
+        #[serde(default = "default_pad_width")]
+        width: f32,
+        #[serde(default = "default_launch_speed")]
+        launch_speed: f32,
+    },
+    /// Speeds the ball up along the ground while it's on the pad, a negative acceleration pushes it backwards
+    Boost {
+        #[serde(default = "default_pad_width")]
+        width: f32,
+        #[serde(default = "default_boost_acceleration")]
+        acceleration: f32,
+    },
+}
+
+fn default_bumper_radius() -> f32 {
+    DEFAULT_BUMPER_RADIUS
+}
+
+fn default_bumper_kick() -> f32 {
+    DEFAULT_BUMPER_KICK
+}
+
+fn default_pad_width() -> f32 {
+    DEFAULT_PAD_WIDTH
+}
+
+fn default_launch_speed() -> f32 {
+    DEFAULT_LAUNCH_SPEED
+}
+
+fn default_boost_acceleration() -> f32 {
+    DEFAULT_BOOST_ACCELERATION
+}
+
+impl Gadget {
+    /// Whether the gadget has a sensible size, it can't be made into a mesh otherwise
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Gadget::Bumper { radius, .. } => radius > 0.0,
+            Gadget::Spring { width, .. } | Gadget::Boost { width, .. } => width > 0.0,
+        }
+    }
+
+    /// How far the centre of the gadget is above its position, pads sit on top of it while bumpers are centred on it
+    fn centre_height(&self) -> f32 {
+        match self {
+            Gadget::Bumper { .. } => 0.0,
+            Gadget::Spring { .. } => SPRING_THICKNESS / 2.0,
+            Gadget::Boost { .. } => BOOST_HEIGHT / 2.0,
+        }
+    }
+
+    pub fn mesh(&self) -> Mesh {
+        match *self {
+            Gadget::Bumper { radius, .. } => Circle::new(radius).into(),
+            Gadget::Spring { width, .. } => Rectangle::new(width, SPRING_THICKNESS).into(),
+            Gadget::Boost { width, .. } => Rectangle::new(width, BOOST_HEIGHT).into(),
+        }
+    }
+
+    pub fn colour(&self) -> Color {
+        match self {
+            Gadget::Bumper { .. } => PINK_500.into(),
+            Gadget::Spring { .. } => YELLOW_400.into(),
+            // See-through so the ground under it still shows
+            Gadget::Boost { .. } => CYAN_400.with_alpha(0.5).into(),
+        }
+    }
+
+    fn collider(&self) -> Collider {
+        match *self {
+            Gadget::Bumper { radius, .. } => Collider::circle(radius),
+            Gadget::Spring { width, .. } => Collider::rectangle(width, SPRING_THICKNESS),
+            Gadget::Boost { width, .. } => Collider::rectangle(width, BOOST_HEIGHT),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Gadget::Bumper { .. } => "Bumper",
+            Gadget::Spring { .. } => "Spring",
+            Gadget::Boost { .. } => "Boost",
+        }
+    }
+
+    fn restitution(&self) -> Restitution {
+        match self {
+            // Max beats the ball's Average rule so bumpers are always bouncy
+            Gadget::Bumper { .. } => {
+                Restitution::new(0.9).with_combine_rule(CoefficientCombine::Max)
+            }
+            Gadget::Spring { .. } | Gadget::Boost { .. } => Restitution::new(0.4),
+        }
+    }
+}
+
+/// A gadget as it's written in a level file
+#[derive(Deserialize)]
+pub struct RawGadget {
+    /// Centre of a bumper, or the middle of the bottom edge of a pad, from the same origin as the level's outline
+    pub position: Vec2,
+    /// Degrees anticlockwise, tilting a pad changes which way it launches or pushes the ball
+    #[serde(default)]
+    pub angle: f32,
+    pub gadget: Gadget,
+}
+
+pub struct LevelGadget {
+    pub gadget: Gadget,
+    pub position: Vec2,
+    /// Radians anticlockwise
+    pub angle: f32,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+}
+
+/// Sent when the ball sets off a bumper or spring, or first rolls onto a boost pad
+#[derive(Event)]
+pub struct GadgetTriggeredEvent(pub Gadget);
+
+/// Spawned as a child of the floor so it's part of the level's static body
+pub fn spawn_gadget(parent: &mut ChildBuilder, gadget: &LevelGadget) {
+    let offset = Rot2::radians(gadget.angle) * Vec2::Y * gadget.gadget.centre_height();
+
+    let mut entity = parent.spawn((
+        Name::new(gadget.gadget.name()),
+        LevelElement,
+        gadget.gadget,
+        Mesh2d(gadget.mesh.clone()),
+        MeshMaterial2d(gadget.material.clone()),
+        gadget.gadget.collider(),
+        gadget.gadget.restitution(),
+        CollidingEntities::default(),
+        // In front of the terrain and its overlay
+        Transform::from_translation((gadget.position + offset).extend(0.02))
+            .with_rotation(Quat::from_rotation_z(gadget.angle)),
+    ));
+
+    // The ball rolls through boost pads rather than over them
+    if matches!(gadget.gadget, Gadget::Boost { .. }) {
+        entity.insert(Sensor);
+    }
+}
+
+fn kick_ball_off_gadgets(
+    mut collision_events: EventReader<CollisionStarted>,
+    mut ball_q: Query<(Entity, &Position, &mut LinearVelocity), With<Ball>>,
+    gadget_q: Query<(&Gadget, &GlobalTransform)>,
+    mut events: EventWriter<GadgetTriggeredEvent>,
+) {
+    let Ok((ball_entity, ball_pos, mut vel)) = ball_q.get_single_mut() else {
+        return;
+    };
+
+    for CollisionStarted(entity1, entity2) in collision_events.read() {
+        let other_entity = if *entity1 == ball_entity {
+            *entity2
+        } else if *entity2 == ball_entity {
+            *entity1
+        } else {
+            continue;
+        };
+
+        let Ok((&gadget, transform)) = gadget_q.get(other_entity) else {
+            continue;
+        };
+
+        match gadget {
+            Gadget::Bumper { kick, .. } => {
+                let away = (ball_pos.0 - transform.translation().truncate()).normalize_or_zero();
+                vel.0 += away * kick;
+            }
+            Gadget::Spring { launch_speed, .. } => {
+                let up = transform.up().truncate();
+                // Whatever the ball was doing across the spring is kept
+                vel.0 = vel.0.reject_from_normalized(up) + up * launch_speed;
+            }
+            Gadget::Boost { .. } => {}
+        }
+
+        events.send(GadgetTriggeredEvent(gadget));
+    }
+}
+
+fn push_ball_along_boost_pads(
+    mut ball_q: Query<(Entity, &mut LinearVelocity), With<Ball>>,
+    gadget_q: Query<(&Gadget, &GlobalTransform, &CollidingEntities)>,
+    time: Res<Time>,
+) {
+    let Ok((ball_entity, mut vel)) = ball_q.get_single_mut() else {
+        return;
+    };
+
+    for (gadget, transform, colliding_entities) in &gadget_q {
+        let Gadget::Boost { acceleration, .. } = *gadget else {
+            continue;
+        };
+
+        if colliding_entities.contains(&ball_entity) {
+            vel.0 += transform.right().truncate() * acceleration * time.delta_secs();
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use bevy::{image::ImageLoaderSettings, math::vec2, prelude::*, utils::SystemTime};
+
+use crate::{
+    app::AppState,
+    course_data::{Course, Courses, Theme, DEFAULT_BACKGROUND, DEFAULT_MUSIC},
+    cup::RawCup,
+    direction::{Direction, DEFAULT_CURVE_RESOLUTION},
+    level_data::{Level, LevelAssets, RawLevelData},
+    physics::RawLevelPhysics,
+    terrain::{tiling_sampler, RawTerrain},
+};
+
+pub struct GeneratorPlugin;
+
+impl Plugin for GeneratorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(AppState::Loading), add_daily_hole);
+    }
+}
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Flat ground the ball starts on
+const TEE_LENGTH: f32 = 150.0;
+const TEE_OFFSET: f32 = 50.0;
+/// Flat ground after the cup before the terrain drops away
+const FINISH_LENGTH: f32 = 70.0;
+const NOTCH_DEPTH: f32 = 75.0;
+/// How far below the lowest point of the surface the bottom of the terrain is
+const FLOOR_THICKNESS: f32 = 300.0;
+
+const MIN_RUN: f32 = 60.0;
+const MAX_RUN: f32 = 200.0;
+const MIN_STEP: f32 = 20.0;
+const MIN_GAP: f32 = 40.0;
+const MIN_GAP_DEPTH: f32 = 40.0;
+const MAX_GAP_DEPTH: f32 = 120.0;
+
+/// What a generated hole can throw at the player
+#[derive(Debug, Clone, Copy)]
+pub struct Difficulty {
+    /// How many slopes, steps and gaps there are between the tee and the cup
+    pub features: usize,
+    /// Steepest slope as rise over run
+    pub max_slope: f32,
+    /// Tallest step up or down
+    pub max_step: f32,
+    /// Widest gap the ball has to clear, zero means there are none
+    pub max_gap: f32,
+    pub cup_width: f32,
+    /// Flat ground before the cup, less of it makes the cup harder to stop in
+    pub cup_run_up: f32,
+}
+
+impl Difficulty {
+    pub const EASY: Difficulty = Difficulty {
+        features: 2,
+        max_slope: 0.5,
+        max_step: 40.0,
+        max_gap: 0.0,
+        cup_width: 50.0,
+        cup_run_up: 150.0,
+    };
+
+    pub const NORMAL: Difficulty = Difficulty {
+        features: 4,
+        max_slope: 1.0,
+        max_step: 80.0,
+        max_gap: 80.0,
+        cup_width: 40.0,
+        cup_run_up: 80.0,
+    };
+
+    pub const HARD: Difficulty = Difficulty {
+        features: 6,
+        max_slope: 2.0,
+        max_step: 150.0,
+        max_gap: 150.0,
+        cup_width: 30.0,
+        cup_run_up: 30.0,
+    };
+}
+
+/// Small deterministic random number generator (SplitMix64), so a seed makes the same hole on every platform
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Between 0 and 1
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    fn below(&mut self, count: usize) -> usize {
+        (self.next_u64() % count as u64) as usize
+    }
+
+    fn sign(&mut self) -> f32 {
+        if self.next_u64() & 1 == 0 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+/// The surface of the hole as it's walked from left to right
+#[derive(Default)]
+struct Outline {
+    directions: Vec<Direction>,
+    position: Vec2,
+    lowest: f32,
+}
+
+impl Outline {
+    fn push(&mut self, offset: Vec2) {
+        self.directions.push(Direction::Line(offset));
+        self.position += offset;
+        self.lowest = self.lowest.min(self.position.y);
+    }
+}
+
+/// Make a hole from a seed. The surface only ever moves right and the terrain is closed off well below it,
+/// so the outline can't cross itself and always triangulates.
+pub fn generate_level(seed: u64, difficulty: &Difficulty) -> RawLevelData {
+    let mut rng = SeededRng(seed);
+    let mut outline = Outline::default();
+
+    outline.push(Vec2::ZERO);
+    outline.push(vec2(TEE_LENGTH, 0.0));
+
+    for _ in 0..difficulty.features {
+        // Flat ground between features keeps vertical walls from running into each other
+        outline.push(vec2(rng.range(MIN_RUN, MAX_RUN), 0.0));
+
+        match rng.below(3) {
+            0 => {
+                let run = rng.range(MIN_RUN, MAX_RUN);
+                let rise = rng.range(-1.0, 1.0) * difficulty.max_slope * run;
+                outline.push(vec2(run, rise));
+            }
+            1 => {
+                let rise = rng.sign() * rng.range(MIN_STEP, difficulty.max_step.max(MIN_STEP));
+                outline.push(vec2(0.0, rise));
+            }
+            _ if difficulty.max_gap >= MIN_GAP => {
+                let width = rng.range(MIN_GAP, difficulty.max_gap);
+                let depth = rng.range(MIN_GAP_DEPTH, MAX_GAP_DEPTH);
+                outline.push(vec2(0.0, -depth));
+                outline.push(vec2(width, 0.0));
+                outline.push(vec2(0.0, depth));
+            }
+            _ => outline.push(vec2(rng.range(MIN_RUN, MAX_RUN), 0.0)),
+        }
+    }
+
+    outline.push(vec2(difficulty.cup_run_up, 0.0));
+
+    let goal_index = outline.directions.len();
+    outline.push(vec2(0.0, -NOTCH_DEPTH));
+    outline.push(vec2(difficulty.cup_width, 0.0));
+    outline.push(vec2(0.0, NOTCH_DEPTH));
+    outline.push(vec2(FINISH_LENGTH, 0.0));
+
+    let bottom = outline.lowest - FLOOR_THICKNESS;
+    outline.push(vec2(0.0, bottom - outline.position.y));
+    outline.push(vec2(-outline.position.x, 0.0));
+    outline.push(vec2(0.0, -bottom));
+
+    RawLevelData {
+        name: Some(format!("Hole {seed}")),
+        par: 2 + difficulty.features as u32 / 3,
+        lives: None,
+        author: None,
+        directions: outline.directions,
+        goal_index,
+        cup: RawCup::default(),
+        alternate_cups: Vec::new(),
+        tee: vec2(TEE_OFFSET, 0.0),
+        alternate_tees: Vec::new(),
+        holes: Vec::new(),
+        islands: Vec::new(),
+        water: Vec::new(),
+        obstacles: Vec::new(),
+        gadgets: Vec::new(),
+        zones: Vec::new(),
+        portals: Vec::new(),
+        breakables: Vec::new(),
+        stars: Vec::new(),
+        checkpoints: Vec::new(),
+        gates: Vec::new(),
+        triggers: Vec::new(),
+        curve_resolution: DEFAULT_CURVE_RESOLUTION,
+        surfaces: Vec::new(),
+        terrain: RawTerrain::default(),
+        physics: RawLevelPhysics::default(),
+    }
+}
+
+/// Generated levels aren't loaded from a file, so their assets go straight into `Assets`
+struct GeneratedLevelAssets<'a> {
+    asset_server: &'a AssetServer,
+    meshes: &'a mut Assets<Mesh>,
+    materials: &'a mut Assets<ColorMaterial>,
+}
+
+impl LevelAssets for GeneratedLevelAssets<'_> {
+    fn add_mesh(&mut self, _label: String, mesh: Mesh) -> Handle<Mesh> {
+        self.meshes.add(mesh)
+    }
+
+    fn add_material(&mut self, _label: String, material: ColorMaterial) -> Handle<ColorMaterial> {
+        self.materials.add(material)
+    }
+
+    fn load_texture(&mut self, path: String) -> Handle<Image> {
+        self.asset_server
+            .load_with_settings(path, |settings: &mut ImageLoaderSettings| {
+                settings.sampler = tiling_sampler();
+            })
+    }
+
+    fn load_sound(&mut self, path: String) -> Handle<AudioSource> {
+        self.asset_server.load(path)
+    }
+}
+
+/// A course with a single generated hole that changes every day
+fn add_daily_hole(
+    asset_server: Res<AssetServer>,
+    mut courses: ResMut<Courses>,
+    mut levels: ResMut<Assets<Level>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let day = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or_default();
+
+    let mut assets = GeneratedLevelAssets {
+        asset_server: &asset_server,
+        meshes: &mut meshes,
+        materials: &mut materials,
+    };
+
+    let level = match generate_level(day, &Difficulty::NORMAL).into_level(&mut assets) {
+        Ok(level) => level,
+        Err(err) => {
+            warn!("Skipping daily hole: {err}");
+            return;
+        }
+    };
+
+    courses.0.push(Course {
+        name: "Daily Hole".to_string(),
+        levels: vec![levels.add(level)],
+        theme: Theme {
+            background: asset_server.load(DEFAULT_BACKGROUND),
+        },
+        music: asset_server.load(DEFAULT_MUSIC),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thumbnail::DiscardedAssets;
+
+    const SEEDS: u64 = 300;
+
+    #[test]
+    fn generated_levels_are_valid() {
+        for difficulty in [Difficulty::EASY, Difficulty::NORMAL, Difficulty::HARD] {
+            for seed in 0..SEEDS {
+                if let Err(err) = generate_level(seed, &difficulty).into_level(&mut DiscardedAssets)
+                {
+                    panic!("Seed {seed} at {difficulty:?} made an invalid level: {err}");
+                }
+            }
+        }
+    }
+}
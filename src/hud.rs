@@ -0,0 +1,126 @@
+use bevy::{color::palettes::css::WHITE, prelude::*};
+
+use crate::{
+    app::AppState,
+    course::NextLevelIndex,
+    level_data::{CurrentLevel, Level},
+    star::{HoleStars, STAR_COLOUR},
+};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup);
+
+        app.add_systems(
+            Update,
+            update_level_info_text
+                .run_if(resource_changed::<NextLevelIndex>.or(on_event::<AssetEvent<Level>>)),
+        );
+        app.add_systems(
+            Update,
+            update_star_count_text.run_if(
+                resource_changed::<HoleStars>
+                    .or(resource_changed::<NextLevelIndex>)
+                    .or(on_event::<AssetEvent<Level>>),
+            ),
+        );
+        app.add_systems(
+            Update,
+            toggle_hud_visibility.run_if(state_changed::<AppState>),
+        );
+    }
+}
+
+#[derive(Component)]
+struct Hud;
+
+#[derive(Component)]
+struct LevelInfoText;
+
+#[derive(Component)]
+struct StarCountText;
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn((
+            Hud,
+            Node {
+                width: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::top(Val::Px(45.0)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LevelInfoText,
+                Text::default(),
+                TextFont::from_font_size(16.0),
+                TextColor::from(WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+            parent.spawn((
+                StarCountText,
+                Text::default(),
+                TextFont::from_font_size(16.0),
+                TextColor::from(STAR_COLOUR),
+            ));
+        });
+}
+
+fn toggle_hud_visibility(
+    mut query: Query<&mut Visibility, With<Hud>>,
+    app_state: Res<State<AppState>>,
+) {
+    let Ok(mut visibility) = query.get_single_mut() else {
+        return;
+    };
+
+    *visibility = match *app_state.get() {
+        AppState::InGame => Visibility::Visible,
+        _ => Visibility::Hidden,
+    }
+}
+
+fn update_level_info_text(
+    current_level: CurrentLevel,
+    mut level_info_text_q: Query<&mut Text, With<LevelInfoText>>,
+) {
+    let Some(level) = current_level.get() else {
+        return;
+    };
+
+    let mut info = format!("{} - Par {}", level.name, level.par);
+
+    if let Some(author) = &level.author {
+        info.push_str(&format!("\nby {author}"));
+    }
+
+    for mut text in &mut level_info_text_q {
+        text.0 = info.clone();
+    }
+}
+
+/// Left empty on levels without any stars
+fn update_star_count_text(
+    current_level: CurrentLevel,
+    hole_stars: Res<HoleStars>,
+    mut star_count_text_q: Query<&mut Text, With<StarCountText>>,
+) {
+    let Some(level) = current_level.get() else {
+        return;
+    };
+
+    let count = if level.stars.is_empty() {
+        String::new()
+    } else {
+        format!("Stars: {}/{}", **hole_stars, level.stars.len())
+    };
+
+    for mut text in &mut star_count_text_q {
+        text.0 = count.clone();
+    }
+}
@@ -1,11 +1,21 @@
 use avian2d::prelude::*;
-use bevy::{math::vec2, prelude::*, render::primitives::Aabb, sprite::Wireframe2d};
+use bevy::{prelude::*, render::primitives::Aabb, sprite::Wireframe2d};
 
 use crate::{
     ball::{Ball, BallResetEvent},
+    breakable::breakable_bundle,
     cam::on_level_resize_zoom,
-    course::NextLevelIndex,
-    level_data::Levels,
+    checkpoint::{checkpoint_bundle, ReachedCheckpoint},
+    gadget::spawn_gadget,
+    level_data::{CurrentLevel, Level},
+    obstacle::obstacle_bundle,
+    physics::LevelPhysics,
+    portal::portal_bundle,
+    star::star_bundle,
+    tee::TeeChoice,
+    trigger::{gate_bundle, spawn_trigger},
+    water::Water,
+    zone::zone_bundle,
 };
 
 #[derive(States, Default, Debug, PartialEq, Eq, Clone, Hash)]
@@ -21,6 +31,7 @@ pub struct LevelPlugin;
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<LevelState>();
+        app.add_event::<CupSunkEvent>();
 
         app.add_systems(Startup, setup);
 
@@ -33,14 +44,33 @@ impl Plugin for LevelPlugin {
             Update,
             tick_level_transition_timer.run_if(in_state(LevelState::Won)),
         );
+
+        app.add_systems(Update, load_level.run_if(current_level_modified));
     }
 }
 
 #[derive(Component)]
 pub struct Floor;
 
+/// The sensor inside one of the level's cups
+#[derive(Component)]
+pub struct Goal {
+    /// Index into the level's `cups`
+    pub index: usize,
+    pub points: u32,
+}
+
+/// Sent when the ball drops into a cup, before the level is won
+#[derive(Event, Debug)]
+pub struct CupSunkEvent {
+    /// Index into the level's `cups`, 0 is the level's own cup
+    pub cup: usize,
+    pub points: u32,
+}
+
+/// Anything spawned from a level's data, these are all despawned when a new level is loaded
 #[derive(Component)]
-pub struct Goal;
+pub struct LevelElement;
 
 #[derive(Component, Default, Deref, DerefMut)]
 pub struct Tee(pub Vec2);
@@ -52,78 +82,64 @@ fn setup(
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
-    let floor = commands
+    commands
         .spawn((
             Name::new("Floor"),
             Floor,
             Mesh2d::default(),
             MeshMaterial2d::<ColorMaterial>::default(),
-            Collider::default(),
             RigidBody::Static,
-            Friction::new(0.4),
-            Restitution::new(0.4),
             Tee::default(),
             Wireframe2d,
         ))
-        .observe(on_level_resize_zoom)
-        .id();
-
-    let ball = Circle::new(BALL_RADIUS);
+        .observe(on_level_resize_zoom);
 
     commands.spawn((
         Name::new("Ball"),
-        Ball,
-        Mesh2d(meshes.add(ball)),
+        ball_body(),
+        Mesh2d(meshes.add(Circle::new(BALL_RADIUS))),
         MeshMaterial2d(materials.add(Color::WHITE)),
+    ));
+}
+
+/// Everything about the ball except how it's drawn, so it rolls the same wherever it's simulated
+pub fn ball_body() -> impl Bundle {
+    let ball = Circle::new(BALL_RADIUS);
+
+    (
+        Ball,
         Position::default(),
         ball.collider(),
         RigidBody::Dynamic,
         Friction::new(0.4),
-        AngularDamping(8.0),
+        LevelPhysics::default().ball_damping(),
         Restitution::new(0.4),
         SweptCcd::NON_LINEAR,
-    ));
-
-    commands
-        .spawn((
-            Name::new("Goal"),
-            Goal,
-            Collider::rectangle(50.0, 50.0),
-            Sensor,
-            CollidingEntities::default(),
-            Transform::default(),
-        ))
-        .set_parent(floor);
+    )
 }
 
 pub fn load_level(
     mut commands: Commands,
-    levels: Res<Levels>,
-    next_level_index: Res<NextLevelIndex>,
+    current_level: CurrentLevel,
     mut level_q: Query<
         (
             Entity,
             &mut Mesh2d,
             &mut MeshMaterial2d<ColorMaterial>,
-            &mut Collider,
             &mut Tee,
         ),
         With<Floor>,
     >,
-    mut goal_q: Query<&mut Transform, With<Goal>>,
+    level_elements_q: Query<Entity, With<LevelElement>>,
+    mut ball_q: Query<(&mut LinearDamping, &mut AngularDamping), With<Ball>>,
+    tee_choice: Res<TeeChoice>,
     mut reset_ball_events: EventWriter<BallResetEvent>,
 ) {
-    if levels.is_empty() {
-        return;
-    }
-
-    let Ok((level_entity, mut mesh, mut material, mut collider, mut tee)) =
-        level_q.get_single_mut()
-    else {
+    let Ok((level_entity, mut mesh, mut material, mut tee)) = level_q.get_single_mut() else {
         return;
     };
 
-    let Some(next_level) = &levels.0.get(**next_level_index) else {
+    let Some(next_level) = current_level.get() else {
         return;
     };
 
@@ -131,24 +147,142 @@ pub fn load_level(
 
     **material = next_level.material.clone();
 
-    *collider = Collider::polyline(next_level.points.clone(), None);
+    for entity in &level_elements_q {
+        commands.entity(entity).despawn_recursive();
+    }
 
-    tee.0 = next_level.tee;
+    commands.entity(level_entity).with_children(|parent| {
+        for (surface, collider) in next_level.surface_colliders() {
+            parent.spawn((
+                Name::new(format!("{surface:?}")),
+                LevelElement,
+                surface,
+                collider,
+                surface.friction(),
+                surface.restitution(),
+                Transform::default(),
+            ));
+        }
 
-    reset_ball_events.send(BallResetEvent);
+        if let Some(overlay) = &next_level.overlay {
+            parent.spawn((
+                Name::new("Overlay"),
+                LevelElement,
+                Mesh2d(overlay.clone()),
+                MeshMaterial2d(next_level.overlay_material.clone()),
+                // Just in front of the terrain
+                Transform::from_xyz(0.0, 0.0, 0.01),
+            ));
+        }
 
-    let mut goal_transform = goal_q.single_mut();
+        for (index, level_cup) in next_level.cups.iter().enumerate() {
+            parent.spawn((
+                Name::new(format!("Goal {index}")),
+                LevelElement,
+                Goal {
+                    index,
+                    points: level_cup.cup.points,
+                },
+                level_cup.cup.collider(),
+                Sensor,
+                CollidingEntities::default(),
+                Transform::from_translation(level_cup.sensor_position().extend(0.0)),
+            ));
+        }
 
-    goal_transform.translation = (next_level.goal_bottom_left + vec2(25.0, 25.0)).extend(0.0);
+        for zone in &next_level.zones {
+            parent.spawn(zone_bundle(zone));
+        }
+
+        for (pair, ends) in next_level.portals.iter().enumerate() {
+            for (end, portal) in ends.iter().enumerate() {
+                parent.spawn(portal_bundle(portal, pair, end));
+            }
+        }
+
+        for breakable in &next_level.breakables {
+            parent.spawn(breakable_bundle(breakable));
+        }
+
+        for gadget in &next_level.gadgets {
+            spawn_gadget(parent, gadget);
+        }
+
+        for (index, star) in next_level.stars.iter().enumerate() {
+            parent.spawn(star_bundle(star, index));
+        }
+
+        for (index, checkpoint) in next_level.checkpoints.iter().enumerate() {
+            parent.spawn(checkpoint_bundle(checkpoint, index));
+        }
+
+        for (index, gate) in next_level.gates.iter().enumerate() {
+            parent.spawn(gate_bundle(gate, index));
+        }
+
+        for (index, trigger) in next_level.triggers.iter().enumerate() {
+            spawn_trigger(parent, trigger, index);
+        }
+
+        for collider in next_level.water_colliders() {
+            parent.spawn((
+                Name::new("Water"),
+                LevelElement,
+                Water,
+                collider,
+                Sensor,
+                Transform::default(),
+            ));
+        }
+    });
+
+    // Obstacles are bodies of their own so they can't be children of the floor
+    for (index, obstacle) in next_level.obstacles.iter().enumerate() {
+        commands.spawn(obstacle_bundle(
+            obstacle,
+            index,
+            next_level.material.clone(),
+        ));
+    }
+
+    tee.0 = tee_choice.pick(&next_level.tees);
+
+    // Every level sets all of these so nothing carries over from the last one
+    commands.insert_resource(next_level.physics.gravity());
+    commands.insert_resource(next_level.physics.sleeping_threshold());
+    commands.insert_resource(ReachedCheckpoint::default());
+
+    if let Ok((mut linear_damping, mut angular_damping)) = ball_q.get_single_mut() {
+        (*linear_damping, *angular_damping) = next_level.physics.ball_damping();
+    }
+
+    reset_ball_events.send(BallResetEvent);
 
     // Recompute Aabb since we changed the mesh
     commands.entity(level_entity).remove::<Aabb>();
 }
 
+/// Rebuild the current level in place whenever its file is changed on disk
+fn current_level_modified(
+    mut asset_events: EventReader<AssetEvent<Level>>,
+    current_level: CurrentLevel,
+) -> bool {
+    let Some(current_level) = current_level.handle() else {
+        return false;
+    };
+
+    asset_events
+        .read()
+        .filter(|event| event.is_modified(current_level))
+        .count()
+        > 0
+}
+
 fn detect_ball_in_goal(
     ball_q: Query<Entity, With<Ball>>,
-    goal_collisions_q: Query<&CollidingEntities, With<Goal>>,
+    goal_collisions_q: Query<(&Goal, &CollidingEntities)>,
     mut next_level_state: ResMut<NextState<LevelState>>,
+    mut cup_sunk_events: EventWriter<CupSunkEvent>,
 ) {
     if goal_collisions_q.is_empty() {
         return;
@@ -156,8 +290,12 @@ fn detect_ball_in_goal(
 
     let ball_entity = ball_q.single();
 
-    for entities in &goal_collisions_q {
+    for (goal, entities) in &goal_collisions_q {
         if entities.contains(&ball_entity) {
+            cup_sunk_events.send(CupSunkEvent {
+                cup: goal.index,
+                points: goal.points,
+            });
             next_level_state.set(LevelState::Won);
             return;
         }
@@ -1,172 +1,979 @@
+use std::path::Path;
+
+use avian2d::prelude::*;
 use bevy::{
-    color::palettes::tailwind::PURPLE_900,
-    math::{
-        bounding::{Aabb2d, BoundingVolume},
-        vec2,
-    },
+    asset::{io::Reader, AssetLoader, LoadContext},
+    color::palettes::tailwind::BLUE_500,
+    ecs::system::SystemParam,
+    image::ImageLoaderSettings,
+    math::bounding::{Aabb2d, BoundingVolume},
     prelude::*,
     render::{mesh::Indices, render_asset::RenderAssetUsages},
 };
 use earcutr::earcut;
+use serde::Deserialize;
 
-use crate::level::BALL_RADIUS;
+use crate::{
+    breakable::RawBreakable,
+    checkpoint::{
+        gate_mesh, LevelCheckpoint, RawCheckpoint, CHECKPOINT_COLOUR, REACHED_CHECKPOINT_COLOUR,
+    },
+    course::{CurrentCourse, NextLevelIndex},
+    course_data::Courses,
+    cup::{Cup, LevelCup, RawAlternateCup, RawCup},
+    direction::{directions_to_points, Direction, DEFAULT_CURVE_RESOLUTION},
+    gadget::{LevelGadget, RawGadget},
+    level::BALL_RADIUS,
+    level_validation::{
+        validate_breakable, validate_checkpoint, validate_gate, validate_level, validate_obstacle,
+        validate_portal, validate_star, validate_trigger, validate_zone, LevelError,
+    },
+    obstacle::Motion,
+    physics::{LevelPhysics, RawLevelPhysics},
+    portal::{portal_colour, portal_mesh, RawPortal},
+    star::{star_mesh, LevelStar, RawStar, STAR_COLOUR},
+    surface::Surface,
+    tee::{LevelTee, RawTee, TeeBox},
+    terrain::{tiling_sampler, RawTerrain, Terrain, TERRAIN_TEXTURE_SIZE},
+    trigger::{LevelGate, LevelTrigger, RawGate, RawTrigger, GATE_COLOUR},
+    zone::{ZoneEffect, ZoneShape, ZONE_ALPHA},
+};
 
 pub struct LevelDataPlugin;
 
 impl Plugin for LevelDataPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Levels>();
-
-        app.add_systems(PreStartup, load_levels);
+        app.init_asset::<Level>().init_asset_loader::<LevelLoader>();
     }
 }
 
-#[derive(Resource, Default, Deref)]
-pub struct Levels(pub Vec<Level>);
+/// The level being played, or the one that's about to be
+#[derive(SystemParam)]
+pub struct CurrentLevel<'w> {
+    courses: Res<'w, Courses>,
+    current_course: Res<'w, CurrentCourse>,
+    level_assets: Res<'w, Assets<Level>>,
+    next_level_index: Res<'w, NextLevelIndex>,
+}
 
+impl CurrentLevel<'_> {
+    pub fn handle(&self) -> Option<&Handle<Level>> {
+        self.courses
+            .get(**self.current_course)
+            .and_then(|course| course.levels.get(**self.next_level_index))
+    }
+
+    pub fn get(&self) -> Option<&Level> {
+        self.handle()
+            .and_then(|handle| self.level_assets.get(handle))
+    }
+}
+
+#[derive(Asset, TypePath)]
 pub struct Level {
+    pub name: String,
+    pub par: u32,
+    /// How many strokes the player gets on this hole, if it's different from the usual amount
+    pub lives: Option<u32>,
+    pub author: Option<String>,
     pub points: Vec<Vec2>,
-    pub goal_bottom_left: Vec2,
-    pub tee: Vec2,
+    /// Caves and tunnels cut out of the terrain
+    pub holes: Vec<Vec<Vec2>>,
+    /// Floating terrain separate from the main outline
+    pub islands: Vec<Vec<Vec2>>,
+    /// Hazards that cost a stroke if the ball lands in them
+    pub water: Vec<Vec<Vec2>>,
+    pub obstacles: Vec<LevelObstacle>,
+    /// Bumpers, springs and boost pads
+    pub gadgets: Vec<LevelGadget>,
+    /// Areas where wind blows or gravity is different
+    pub zones: Vec<LevelZone>,
+    /// Pairs of portals, going into either end brings the ball out of the other
+    pub portals: Vec<[LevelPortal; 2]>,
+    /// Walls the ball can smash through if it hits them hard enough
+    pub breakables: Vec<LevelBreakable>,
+    /// Pickups the ball can collect on its way to the cup
+    pub stars: Vec<LevelStar>,
+    /// Places part way along the hole that the ball goes back to instead of the tee, once it's stopped past them
+    pub checkpoints: Vec<LevelCheckpoint>,
+    /// Walls that triggers can open
+    pub gates: Vec<LevelGate>,
+    /// Areas and buttons that do something when the ball touches them
+    pub triggers: Vec<LevelTrigger>,
+    /// What each segment of the outline is made of, `surfaces[i]` goes from `points[i]` to `points[i + 1]`
+    pub surfaces: Vec<Surface>,
+    /// The level's own cup comes first, followed by its alternate cups
+    pub cups: Vec<LevelCup>,
+    /// The regular tee comes first, followed by the alternate tees
+    pub tees: Vec<LevelTee>,
+    pub terrain: Terrain,
+    pub physics: LevelPhysics,
+    /// The terrain on its own, textured by `material`
     pub mesh: Handle<Mesh>,
     pub material: Handle<ColorMaterial>,
+    /// Water and surface strips drawn over the terrain in their own colours, if the level has any
+    pub overlay: Option<Handle<Mesh>>,
+    pub overlay_material: Handle<ColorMaterial>,
 }
 
-fn load_levels(
-    mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-) {
-    commands.insert_resource(Levels(
-        vec![level_1(), level_2(), level_3()]
+/// A piece of terrain that moves by itself
+pub struct LevelObstacle {
+    /// Outline around the point it moves and turns about
+    pub points: Vec<Vec2>,
+    pub mesh: Handle<Mesh>,
+    pub position: Vec2,
+    pub motion: Motion,
+    pub waiting: bool,
+}
+
+impl Level {
+    /// One collider for each surface the outline uses, holes and islands are all fairway
+    pub fn surface_colliders(&self) -> Vec<(Surface, Collider)> {
+        let mut colliders: Vec<(Surface, Collider)> = Surface::ALL
             .into_iter()
-            .map(
-                |RawLevelData {
-                     directions,
-                     goal_index,
-                     tee,
-                 }| {
-                    let points = directions_to_points(directions);
-                    let goal_bottom_left = points[goal_index];
-                    let mesh = meshes.add(convert_level_points_to_mesh(&points));
-                    let tee = tee + points[0] + Vec2::Y * BALL_RADIUS;
-                    Level {
-                        points,
-                        goal_bottom_left,
-                        tee,
-                        mesh,
-                        material: materials.add(ColorMaterial::from_color(PURPLE_900)),
-                    }
-                },
-            )
-            .collect(),
-    ));
-}
-
-struct RawLevelData {
-    directions: Vec<Vec2>,
-    goal_index: usize,
-    tee: Vec2,
-}
-
-fn level_1() -> RawLevelData {
-    RawLevelData {
-        directions: vec![
-            Vec2::ZERO,
-            Vec2::X * 300.0,
-            vec2(100.0, 50.0),
-            Vec2::X * 200.0,
-            Vec2::Y * -75.0,
-            Vec2::X * 50.0,
-            Vec2::Y * 75.0,
-            Vec2::X * 70.0,
-            Vec2::Y * -540.0,
-            Vec2::X * -720.0,
-            Vec2::Y * 490.0,
-        ],
-        goal_index: 4,
-        tee: vec2(50.0, 0.0),
-    }
-}
-
-fn level_2() -> RawLevelData {
-    RawLevelData {
-        directions: vec![
-            Vec2::ZERO,
-            Vec2::X * 350.0,
-            vec2(100.0, 150.0),
-            Vec2::X * 200.0,
-            Vec2::Y * -75.0,
-            Vec2::X * 50.0,
-            Vec2::Y * 75.0,
-            Vec2::X * 70.0,
-            Vec2::Y * -640.0,
-            Vec2::X * -770.0,
-            Vec2::Y * 490.0,
-        ],
-        goal_index: 4,
-        tee: vec2(50.0, 0.0),
-    }
-}
-
-fn level_3() -> RawLevelData {
-    RawLevelData {
-        directions: vec![
-            Vec2::ZERO,
-            Vec2::X * 150.0,
-            vec2(50.0, -50.0),
-            vec2(75.0, 50.0),
-            vec2(75.0, -50.0),
-            vec2(50.0, 50.0),
-            Vec2::X * 50.0,
-            Vec2::Y * -75.0,
-            Vec2::X * 50.0,
-            Vec2::Y * 75.0,
-            Vec2::X * 50.0,
-            vec2(20.0, 20.0),
-            Vec2::Y * -400.0,
-            Vec2::X * -570.0,
-            Vec2::Y * 380.0,
-        ],
-        goal_index: 7,
-        tee: vec2(50.0, 0.0),
-    }
-}
-
-/// Turn a list of vectors of how to go from one coordinate to the next into a list of those coordinates centred at the shape's centre
-fn directions_to_points(directions: Vec<Vec2>) -> Vec<Vec2> {
-    let points: Vec<_> = directions
-        .into_iter()
-        .scan(Vec2::ZERO, |running, vec| {
-            *running += vec;
-            Some(*running)
+            .filter_map(|surface| {
+                let indices: Vec<[u32; 2]> = self
+                    .surfaces
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &s)| s == surface)
+                    .map(|(i, _)| [i as u32, i as u32 + 1])
+                    .collect();
+
+                if indices.is_empty() {
+                    return None;
+                }
+
+                Some((
+                    surface,
+                    Collider::polyline(self.points.clone(), Some(indices)),
+                ))
+            })
+            .collect();
+
+        colliders.extend(
+            self.holes
+                .iter()
+                .chain(&self.islands)
+                .map(|points| (Surface::Fairway, Collider::polyline(points.clone(), None))),
+        );
+
+        colliders
+    }
+
+    /// Solid sensor shapes filling each body of water
+    pub fn water_colliders(&self) -> Vec<Collider> {
+        self.water
+            .iter()
+            .filter_map(|points| polygon_collider(points))
+            .collect()
+    }
+}
+
+pub struct LevelZone {
+    pub points: Vec<Vec2>,
+    pub effect: ZoneEffect,
+    pub mesh: Handle<Mesh>,
+    /// Each zone has its own so gusts can fade it in and out
+    pub material: Handle<ColorMaterial>,
+}
+
+pub struct LevelPortal {
+    pub position: Vec2,
+    /// Radians anticlockwise, 0 faces up
+    pub angle: f32,
+    pub width: f32,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+}
+
+pub struct LevelBreakable {
+    pub points: Vec<Vec2>,
+    pub strength: f32,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+}
+
+/// A collider filling the whole polygon rather than just its outline
+pub fn polygon_collider(points: &[Vec2]) -> Option<Collider> {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    triangulate(points, &[], &mut vertices, &mut indices).ok()?;
+
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+
+    Some(Collider::trimesh(vertices, triangles))
+}
+
+/// The triangles a polygon is split into, or none if it can't be split up
+pub fn triangles(points: &[Vec2]) -> Vec<[Vec2; 3]> {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    if triangulate(points, &[], &mut vertices, &mut indices).is_err() {
+        return Vec::new();
+    }
+
+    indices
+        .chunks_exact(3)
+        .map(|triangle| triangle.map(|i| vertices[i as usize]))
+        .collect()
+}
+
+/// A level as it's written in a `.level.json` file
+#[derive(Deserialize)]
+pub struct RawLevelData {
+    /// Defaults to the name of the file
+    pub name: Option<String>,
+    pub par: u32,
+    pub lives: Option<u32>,
+    pub author: Option<String>,
+    pub directions: Vec<Direction>,
+    /// Index of the direction that ends at the bottom left of the goal
+    pub goal_index: usize,
+    /// Size and sensor shape of the goal, it has to fit the notch at `goal_index`
+    #[serde(default)]
+    pub cup: RawCup,
+    /// Other cups the hole can be finished in, each in its own notch
+    #[serde(default)]
+    pub alternate_cups: Vec<RawAlternateCup>,
+    /// Where the ball starts from the regular tee box, relative to the start of the outline
+    pub tee: Vec2,
+    #[serde(default)]
+    pub alternate_tees: Vec<RawTee>,
+    /// Each hole and island is its own list of directions, starting from the same origin as the outline
+    #[serde(default)]
+    pub holes: Vec<Vec<Direction>>,
+    #[serde(default)]
+    pub islands: Vec<Vec<Direction>>,
+    #[serde(default)]
+    pub water: Vec<Vec<Direction>>,
+    #[serde(default)]
+    pub obstacles: Vec<RawObstacle>,
+    #[serde(default)]
+    pub gadgets: Vec<RawGadget>,
+    #[serde(default)]
+    pub zones: Vec<RawZone>,
+    #[serde(default)]
+    pub portals: Vec<[RawPortal; 2]>,
+    #[serde(default)]
+    pub breakables: Vec<RawBreakable>,
+    #[serde(default)]
+    pub stars: Vec<RawStar>,
+    #[serde(default)]
+    pub checkpoints: Vec<RawCheckpoint>,
+    #[serde(default)]
+    pub gates: Vec<RawGate>,
+    #[serde(default)]
+    pub triggers: Vec<RawTrigger>,
+    /// How many straight segments each curve is broken into
+    #[serde(default = "default_curve_resolution")]
+    pub curve_resolution: u32,
+    /// Anything not covered here is fairway
+    #[serde(default)]
+    pub surfaces: Vec<SurfaceRange>,
+    #[serde(default)]
+    pub terrain: RawTerrain,
+    /// Gravity, sleeping thresholds and ball damping for this level only
+    #[serde(default)]
+    pub physics: RawLevelPhysics,
+}
+
+#[derive(Deserialize)]
+pub struct RawObstacle {
+    /// Outline of the obstacle, starting from the point it moves and turns about
+    pub directions: Vec<Direction>,
+    /// Where the obstacle starts, from the same origin as the level's outline
+    pub position: Vec2,
+    pub motion: Motion,
+    /// Doesn't move until a trigger starts it
+    #[serde(default)]
+    pub waiting: bool,
+}
+
+#[derive(Deserialize)]
+pub struct RawZone {
+    pub shape: ZoneShape,
+    pub effect: ZoneEffect,
+}
+
+/// Directions `from` to `to` inclusive are made of `surface`
+#[derive(Deserialize)]
+pub struct SurfaceRange {
+    pub from: usize,
+    pub to: Option<usize>,
+    pub surface: Surface,
+}
+
+fn default_curve_resolution() -> u32 {
+    DEFAULT_CURVE_RESOLUTION
+}
+
+/// Somewhere to put the meshes and materials a level is built with, so levels can be built by an asset loader
+/// or straight into `Assets`
+pub trait LevelAssets {
+    fn add_mesh(&mut self, label: String, mesh: Mesh) -> Handle<Mesh>;
+    fn add_material(&mut self, label: String, material: ColorMaterial) -> Handle<ColorMaterial>;
+    /// Load a texture that tiles, `path` is from the assets folder
+    fn load_texture(&mut self, path: String) -> Handle<Image>;
+    fn load_sound(&mut self, path: String) -> Handle<AudioSource>;
+}
+
+impl LevelAssets for LoadContext<'_> {
+    fn add_mesh(&mut self, label: String, mesh: Mesh) -> Handle<Mesh> {
+        self.add_labeled_asset(label, mesh)
+    }
+
+    fn add_material(&mut self, label: String, material: ColorMaterial) -> Handle<ColorMaterial> {
+        self.add_labeled_asset(label, material)
+    }
+
+    fn load_texture(&mut self, path: String) -> Handle<Image> {
+        self.loader()
+            .with_settings(|settings: &mut ImageLoaderSettings| {
+                settings.sampler = tiling_sampler();
+            })
+            .load(path)
+    }
+
+    fn load_sound(&mut self, path: String) -> Handle<AudioSource> {
+        self.load(path)
+    }
+}
+
+impl RawLevelData {
+    /// Turn the directions into points and build the level's meshes and materials
+    pub fn into_level(self, assets: &mut impl LevelAssets) -> Result<Level, LevelError> {
+        let RawLevelData {
+            name,
+            par,
+            lives,
+            author,
+            directions,
+            goal_index,
+            cup,
+            alternate_cups,
+            tee,
+            alternate_tees,
+            holes,
+            islands,
+            water,
+            obstacles,
+            gadgets,
+            zones,
+            portals,
+            breakables,
+            stars,
+            checkpoints,
+            gates,
+            triggers,
+            curve_resolution,
+            surfaces: surface_ranges,
+            terrain,
+            physics,
+        } = self;
+
+        if lives == Some(0) {
+            return Err(LevelError::NoLives);
+        }
+
+        let terrain = terrain.resolve()?;
+
+        let (points, direction_ends) = directions_to_points(&directions, curve_resolution);
+        let centre = level_centre(&points);
+        let centred =
+            |points: Vec<Vec2>| -> Vec<Vec2> { points.into_iter().map(|p| p - centre).collect() };
+
+        let points = centred(points);
+        let holes: Vec<_> = holes
+            .iter()
+            .map(|hole| centred(directions_to_points(hole, curve_resolution).0))
+            .collect();
+        let islands: Vec<_> = islands
+            .iter()
+            .map(|island| centred(directions_to_points(island, curve_resolution).0))
+            .collect();
+        let water: Vec<_> = water
+            .iter()
+            .map(|pool| centred(directions_to_points(pool, curve_resolution).0))
+            .collect();
+
+        let tee_origin = points.first().copied().unwrap_or_default() + Vec2::Y * BALL_RADIUS;
+        let tees: Vec<LevelTee> = std::iter::once(RawTee {
+            position: tee,
+            tee_box: TeeBox::Regular,
+        })
+        .chain(alternate_tees)
+        .map(|tee| LevelTee {
+            position: tee.position + tee_origin,
+            tee_box: tee.tee_box,
         })
         .collect();
 
-    let centre = Aabb2d::from_point_cloud(Isometry2d::IDENTITY, &points).center();
+        let raw_cups: Vec<RawAlternateCup> = std::iter::once(RawAlternateCup { goal_index, cup })
+            .chain(alternate_cups)
+            .collect();
+
+        let mut goal_indices = Vec::with_capacity(raw_cups.len());
+
+        for (i, raw_cup) in raw_cups.iter().enumerate() {
+            let Some(&goal_index) = direction_ends.get(raw_cup.goal_index) else {
+                return Err(LevelError::GoalIndexOutOfRange {
+                    index: raw_cup.goal_index,
+                    len: directions.len(),
+                });
+            };
+
+            if raw_cups[..i]
+                .iter()
+                .any(|other| other.goal_index == raw_cup.goal_index)
+            {
+                return Err(LevelError::DuplicateCup {
+                    goal_index: raw_cup.goal_index,
+                });
+            }
+
+            goal_indices.push(goal_index);
+        }
+
+        let mut surfaces = vec![Surface::default(); points.len().saturating_sub(1)];
+
+        for SurfaceRange { from, to, surface } in surface_ranges {
+            let to = to.unwrap_or(from);
+
+            if from > to || to >= direction_ends.len() {
+                return Err(LevelError::SurfaceOutOfRange {
+                    from,
+                    to,
+                    len: directions.len(),
+                });
+            }
+
+            let start = if from == 0 {
+                0
+            } else {
+                direction_ends[from - 1]
+            };
+            surfaces[start..direction_ends[to]].fill(surface);
+        }
+
+        let tee_positions: Vec<Vec2> = tees.iter().map(|tee| tee.position).collect();
+        validate_level(
+            &points,
+            &holes,
+            &islands,
+            &water,
+            &goal_indices,
+            &tee_positions,
+        )?;
+
+        let cups = raw_cups
+            .iter()
+            .zip(goal_indices)
+            .map(|(raw_cup, goal_index)| {
+                Ok(LevelCup {
+                    bottom_left: points[goal_index],
+                    cup: Cup::fit_to_notch(raw_cup.cup, &points, goal_index)?,
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let mesh = assets.add_mesh(
+            "mesh".to_string(),
+            convert_terrain_to_mesh(&points, &holes, &islands)?,
+        );
+        let overlay = convert_overlay_to_mesh(&points, &water, &surfaces)?
+            .map(|overlay| assets.add_mesh("overlay".to_string(), overlay));
+
+        let texture = terrain
+            .texture
+            .clone()
+            .map(|path| assets.load_texture(path));
+        let material = assets.add_material(
+            "material".to_string(),
+            ColorMaterial {
+                color: terrain.colour.into(),
+                texture: texture.clone(),
+                ..default()
+            },
+        );
+        // Breakable walls look like the terrain but stand out from it a little
+        let breakable_material = assets.add_material(
+            "breakable_material".to_string(),
+            ColorMaterial {
+                color: terrain.colour.lighter(BREAKABLE_LIGHTEN).into(),
+                texture,
+                ..default()
+            },
+        );
+        // The overlay's colours come from its vertices so its material is left white
+        let overlay_material =
+            assets.add_material("overlay_material".to_string(), ColorMaterial::default());
+
+        let obstacles = obstacles
+            .into_iter()
+            .enumerate()
+            .map(|(index, obstacle)| {
+                let points = directions_to_points(&obstacle.directions, curve_resolution).0;
+
+                validate_obstacle(index, &points, &obstacle.motion)?;
+
+                let mesh = assets.add_mesh(
+                    format!("obstacle{index}"),
+                    convert_terrain_to_mesh(&points, &[], &[])?,
+                );
+
+                Ok(LevelObstacle {
+                    points,
+                    mesh,
+                    position: obstacle.position - centre,
+                    motion: obstacle.motion,
+                    waiting: obstacle.waiting,
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let gadgets = gadgets
+            .into_iter()
+            .enumerate()
+            .map(|(index, raw_gadget)| {
+                if !raw_gadget.gadget.is_valid() {
+                    return Err(LevelError::InvalidGadget { index });
+                }
+
+                Ok(LevelGadget {
+                    gadget: raw_gadget.gadget,
+                    position: raw_gadget.position - centre,
+                    angle: raw_gadget.angle.to_radians(),
+                    mesh: assets.add_mesh(format!("gadget{index}"), raw_gadget.gadget.mesh()),
+                    material: assets.add_material(
+                        format!("gadget{index}_material"),
+                        ColorMaterial::from_color(raw_gadget.gadget.colour()),
+                    ),
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let zones = zones
+            .into_iter()
+            .enumerate()
+            .map(|(index, zone)| {
+                let points = centred(zone.shape.points(curve_resolution));
+
+                validate_zone(index, &points, &zone.effect)?;
+
+                Ok(LevelZone {
+                    mesh: assets.add_mesh(
+                        format!("zone{index}"),
+                        convert_terrain_to_mesh(&points, &[], &[])?,
+                    ),
+                    material: assets.add_material(
+                        format!("zone{index}_material"),
+                        ColorMaterial::from_color(zone.effect.colour().with_alpha(ZONE_ALPHA)),
+                    ),
+                    points,
+                    effect: zone.effect,
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
 
-    points.into_iter().map(|p| p - centre).collect()
+        let portals = portals
+            .into_iter()
+            .enumerate()
+            .map(|(pair, ends)| {
+                let material = assets.add_material(
+                    format!("portal{pair}_material"),
+                    ColorMaterial::from_color(portal_colour(pair)),
+                );
+                let mut level_portal = |end: usize, portal: RawPortal| LevelPortal {
+                    position: portal.position - centre,
+                    angle: portal.angle.to_radians(),
+                    width: portal.width,
+                    mesh: assets.add_mesh(format!("portal{pair}_{end}"), portal_mesh(portal.width)),
+                    material: material.clone(),
+                };
+                let [first, second] = ends;
+                let ends = [level_portal(0, first), level_portal(1, second)];
+
+                validate_portal(pair, &ends, &points)?;
+
+                Ok(ends)
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let breakables = breakables
+            .into_iter()
+            .enumerate()
+            .map(|(index, breakable)| {
+                let points =
+                    centred(directions_to_points(&breakable.directions, curve_resolution).0);
+
+                validate_breakable(index, &points)?;
+
+                Ok(LevelBreakable {
+                    mesh: assets.add_mesh(
+                        format!("breakable{index}"),
+                        convert_terrain_to_mesh(&points, &[], &[])?,
+                    ),
+                    material: breakable_material.clone(),
+                    points,
+                    strength: breakable.strength,
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let star_mesh = assets.add_mesh("star".to_string(), star_mesh());
+        let star_material = assets.add_material(
+            "star_material".to_string(),
+            ColorMaterial::from_color(STAR_COLOUR),
+        );
+
+        let stars = stars
+            .into_iter()
+            .enumerate()
+            .map(|(index, star)| {
+                let position = star.position - centre;
+
+                validate_star(index, position, &points)?;
+
+                Ok(LevelStar {
+                    position,
+                    mesh: star_mesh.clone(),
+                    material: star_material.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let checkpoint_material = assets.add_material(
+            "checkpoint_material".to_string(),
+            ColorMaterial::from_color(CHECKPOINT_COLOUR),
+        );
+        let reached_checkpoint_material = assets.add_material(
+            "reached_checkpoint_material".to_string(),
+            ColorMaterial::from_color(REACHED_CHECKPOINT_COLOUR),
+        );
+
+        let checkpoints = checkpoints
+            .into_iter()
+            .enumerate()
+            .map(|(index, checkpoint)| {
+                let level_checkpoint = LevelCheckpoint {
+                    position: checkpoint.position - centre,
+                    height: checkpoint.height,
+                    mesh: assets.add_mesh(
+                        format!("checkpoint{index}"),
+                        gate_mesh(checkpoint.height.max(0.0)),
+                    ),
+                    material: checkpoint_material.clone(),
+                    reached_material: reached_checkpoint_material.clone(),
+                };
+
+                validate_checkpoint(index, &level_checkpoint, &points)?;
+
+                Ok(level_checkpoint)
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let gate_material = assets.add_material(
+            "gate_material".to_string(),
+            ColorMaterial::from_color(GATE_COLOUR),
+        );
+
+        let gates = gates
+            .into_iter()
+            .enumerate()
+            .map(|(index, gate)| {
+                let points = centred(directions_to_points(&gate.directions, curve_resolution).0);
+
+                validate_gate(index, &points)?;
+
+                Ok(LevelGate {
+                    mesh: assets.add_mesh(
+                        format!("gate{index}"),
+                        convert_terrain_to_mesh(&points, &[], &[])?,
+                    ),
+                    material: gate_material.clone(),
+                    points,
+                })
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let triggers = triggers
+            .into_iter()
+            .enumerate()
+            .map(|(index, trigger)| {
+                let points = centred(trigger.shape.points(curve_resolution));
+
+                let level_trigger = LevelTrigger {
+                    mesh: assets.add_mesh(
+                        format!("trigger{index}"),
+                        convert_terrain_to_mesh(&points, &[], &[])?,
+                    ),
+                    material: assets.add_material(
+                        format!("trigger{index}_material"),
+                        ColorMaterial::from_color(trigger.kind.colour()),
+                    ),
+                    points,
+                    kind: trigger.kind,
+                    once: trigger.once,
+                    actions: trigger
+                        .actions
+                        .into_iter()
+                        .map(|action| action.resolve(|path| assets.load_sound(path)))
+                        .collect(),
+                };
+
+                validate_trigger(index, &level_trigger, gates.len(), obstacles.len())?;
+
+                Ok(level_trigger)
+            })
+            .collect::<Result<Vec<_>, LevelError>>()?;
+
+        let name = name.unwrap_or_default();
+
+        Ok(Level {
+            name,
+            par,
+            lives,
+            author,
+            points,
+            holes,
+            islands,
+            water,
+            obstacles,
+            gadgets,
+            zones,
+            portals,
+            breakables,
+            stars,
+            checkpoints,
+            gates,
+            triggers,
+            surfaces,
+            cups,
+            tees,
+            terrain,
+            physics: physics.resolve(),
+            mesh,
+            material,
+            overlay,
+            overlay_material,
+        })
+    }
+}
+
+#[derive(Default)]
+struct LevelLoader;
+
+#[derive(Debug)]
+pub enum LevelLoaderError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Invalid(LevelError),
+}
+
+impl std::fmt::Display for LevelLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelLoaderError::Io(err) => write!(f, "Could not read level file: {err}"),
+            LevelLoaderError::Json(err) => write!(f, "Could not parse level file: {err}"),
+            LevelLoaderError::Invalid(err) => write!(f, "Invalid level: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LevelLoaderError {}
+
+impl From<std::io::Error> for LevelLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        LevelLoaderError::Io(err)
+    }
 }
 
-fn convert_level_points_to_mesh(points: &Vec<Vec2>) -> Mesh {
+impl From<serde_json::Error> for LevelLoaderError {
+    fn from(err: serde_json::Error) -> Self {
+        LevelLoaderError::Json(err)
+    }
+}
+
+impl From<LevelError> for LevelLoaderError {
+    fn from(err: LevelError) -> Self {
+        LevelLoaderError::Invalid(err)
+    }
+}
+
+impl AssetLoader for LevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Level, LevelLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut raw_level: RawLevelData = serde_json::from_slice(&bytes)?;
+        raw_level
+            .name
+            .get_or_insert_with(|| level_name_from_path(load_context.path()));
+
+        Ok(raw_level.into_level(load_context)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.json"]
+    }
+}
+
+/// `levels/classic/01.level.json` becomes `01`
+pub fn level_name_from_path(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The centre of the outline's bounding box, every part of the level is moved so this is at the origin
+fn level_centre(points: &[Vec2]) -> Vec2 {
+    if points.is_empty() {
+        return Vec2::ZERO;
+    }
+
+    Aabb2d::from_point_cloud(Isometry2d::IDENTITY, points).center()
+}
+
+pub const WATER_COLOUR: Srgba = BLUE_500;
+
+/// How much lighter breakable walls are than the terrain
+const BREAKABLE_LIGHTEN: f32 = 0.1;
+
+/// How far into the terrain the coloured strip along non-fairway surfaces goes
+const SURFACE_STRIP_THICKNESS: f32 = 6.0;
+
+fn convert_terrain_to_mesh(
+    points: &[Vec2],
+    holes: &[Vec<Vec2>],
+    islands: &[Vec<Vec2>],
+) -> Result<Mesh, LevelError> {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    triangulate(points, holes, &mut vertices, &mut indices)?;
+
+    for island in islands {
+        triangulate(island, &[], &mut vertices, &mut indices)?;
+    }
+
+    // Texture coordinates follow the world so textures tile at the same size on every level
+    let uvs: Vec<[f32; 2]> = vertices
+        .iter()
+        .map(|&p| (p / TERRAIN_TEXTURE_SIZE * Vec2::new(1.0, -1.0)).to_array())
+        .collect();
+
+    let mut mesh = mesh_from_vertices(&vertices, indices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+
+    Ok(mesh)
+}
+
+/// Water and the coloured strips along non-fairway surfaces, or `None` if there aren't any
+fn convert_overlay_to_mesh(
+    points: &[Vec2],
+    water: &[Vec<Vec2>],
+    surfaces: &[Surface],
+) -> Result<Option<Mesh>, LevelError> {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for pool in water {
+        triangulate(pool, &[], &mut vertices, &mut indices)?;
+    }
+
+    let mut colours = vec![Color::from(WATER_COLOUR); vertices.len()];
+
+    // Clockwise outlines have the terrain on the right of each segment
+    let inward_sign = if signed_area(points) < 0.0 { -1.0 } else { 1.0 };
+
+    for (segment, surface) in points.windows(2).zip(surfaces) {
+        let Some(colour) = surface.colour() else {
+            continue;
+        };
+
+        let (a, b) = (segment[0], segment[1]);
+        let inward = (b - a).perp().normalize_or_zero() * inward_sign * SURFACE_STRIP_THICKNESS;
+
+        let offset = vertices.len() as u32;
+        vertices.extend([a, b, b + inward, a + inward]);
+        colours.extend([colour; 4]);
+        indices.extend([0, 1, 2, 0, 2, 3].map(|i| i + offset));
+    }
+
+    if vertices.is_empty() {
+        return Ok(None);
+    }
+
+    let colours: Vec<[f32; 4]> = colours
+        .into_iter()
+        .map(|colour| colour.to_linear().to_f32_array())
+        .collect();
+
+    let mut mesh = mesh_from_vertices(&vertices, indices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colours);
+
+    Ok(Some(mesh))
+}
+
+fn mesh_from_vertices(vertices: &[Vec2], indices: Vec<u32>) -> Mesh {
     let mut mesh = Mesh::new(
         bevy::render::mesh::PrimitiveTopology::TriangleList,
         RenderAssetUsages::default(),
     );
 
-    let positions: Vec<[f32; 3]> = points.iter().map(|&p| [p.x, p.y, 0.0]).collect();
+    let positions: Vec<[f32; 3]> = vertices.iter().map(|&p| [p.x, p.y, 0.0]).collect();
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_indices(Indices::U32(indices));
 
-    let uvs: Vec<[f32; 2]> = points.iter().map(|&p| p.to_array()).collect();
-    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh
+}
 
-    let flattened_points: Vec<f32> = points.iter().flat_map(|p| p.to_array().to_vec()).collect();
+/// Positive when `points` go anticlockwise
+pub fn signed_area(points: &[Vec2]) -> f32 {
+    points.windows(2).map(|w| w[0].perp_dot(w[1])).sum::<f32>() / 2.0
+}
 
-    let indices = earcut(&flattened_points, &Vec::new(), 2)
-        .map(|x| x.into_iter().map(|i| i as u32).collect::<Vec<_>>())
-        .unwrap_or_else(|_| panic!("Could not make mesh with these invalid points {:?}", points));
+/// Triangulate a polygon with `holes` cut out of it, adding its vertices and triangle indices onto the ones given
+fn triangulate(
+    outline: &[Vec2],
+    holes: &[Vec<Vec2>],
+    vertices: &mut Vec<Vec2>,
+    indices: &mut Vec<u32>,
+) -> Result<(), LevelError> {
+    let offset = vertices.len() as u32;
 
-    mesh.insert_indices(Indices::U32(indices));
+    let mut polygon = outline.to_vec();
+    let mut hole_indices = Vec::new();
 
-    mesh
+    for hole in holes {
+        hole_indices.push(polygon.len());
+        polygon.extend(hole);
+    }
+
+    let flattened_points: Vec<f32> = polygon.iter().flat_map(|p| p.to_array()).collect();
+
+    let triangles =
+        earcut(&flattened_points, &hole_indices, 2).map_err(|_| LevelError::Triangulation)?;
+
+    indices.extend(triangles.into_iter().map(|i| i as u32 + offset));
+    vertices.extend(polygon);
+
+    Ok(())
 }
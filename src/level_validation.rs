@@ -0,0 +1,708 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::{
+    checkpoint::LevelCheckpoint,
+    level::BALL_RADIUS,
+    level_data::LevelPortal,
+    obstacle::Motion,
+    portal::{EXIT_CLEARANCE, PORTAL_DEPTH},
+    trigger::{LevelAction, LevelTrigger},
+    zone::ZoneEffect,
+};
+
+/// How far apart the first and last points can be for the outline to still count as closed
+const CLOSED_TOLERANCE: f32 = 0.01;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelError {
+    TooFewPoints {
+        count: usize,
+    },
+    NotClosed {
+        gap: Vec2,
+    },
+    DegenerateSegment {
+        index: usize,
+    },
+    SelfIntersecting {
+        first: usize,
+        second: usize,
+    },
+    GoalIndexOutOfRange {
+        index: usize,
+        len: usize,
+    },
+    TeeInsideTerrain {
+        tee: Vec2,
+    },
+    InvalidHole {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    InvalidIsland {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    InvalidWater {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    InvalidObstacle {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    NoMotion {
+        index: usize,
+    },
+    InvalidGadget {
+        index: usize,
+    },
+    InvalidZone {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    NoGustPeriod {
+        index: usize,
+    },
+    InvalidPortal {
+        pair: usize,
+    },
+    InvalidBreakable {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    PortalOutOfBounds {
+        pair: usize,
+    },
+    PortalExitInsideTerrain {
+        pair: usize,
+    },
+    StarOutOfBounds {
+        index: usize,
+    },
+    StarInsideTerrain {
+        index: usize,
+    },
+    InvalidCheckpoint {
+        index: usize,
+    },
+    InvalidGate {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    InvalidTrigger {
+        index: usize,
+        error: Box<LevelError>,
+    },
+    MissingActionTarget {
+        trigger: usize,
+        action: usize,
+    },
+    CheckpointOutOfBounds {
+        index: usize,
+    },
+    HoleOutsideTerrain {
+        index: usize,
+    },
+    WaterOutsideLevel {
+        index: usize,
+    },
+    WaterInsideTerrain {
+        index: usize,
+    },
+    IslandInsideTerrain {
+        index: usize,
+    },
+    SurfaceOutOfRange {
+        from: usize,
+        to: usize,
+        len: usize,
+    },
+    InvalidColour {
+        colour: String,
+    },
+    NoNotch,
+    NoLives,
+    DuplicateCup {
+        goal_index: usize,
+    },
+    CupWidthMismatch {
+        width: f32,
+        notch_width: f32,
+    },
+    CupTooDeep {
+        depth: f32,
+        notch_depth: f32,
+    },
+    Triangulation,
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::TooFewPoints { count } => {
+                write!(f, "Outline needs at least 4 points but has {count}")
+            }
+            LevelError::NotClosed { gap } => {
+                write!(f, "Outline doesn't end where it starts, it's off by {gap}")
+            }
+            LevelError::DegenerateSegment { index } => {
+                write!(f, "Segment {index} has no length")
+            }
+            LevelError::SelfIntersecting { first, second } => {
+                write!(f, "Segments {first} and {second} cross each other")
+            }
+            LevelError::GoalIndexOutOfRange { index, len } => {
+                write!(
+                    f,
+                    "Goal index {index} is past the end of the outline, which is {len} long"
+                )
+            }
+            LevelError::TeeInsideTerrain { tee } => {
+                write!(f, "Tee at {tee} is inside the terrain")
+            }
+            LevelError::InvalidHole { index, error } => write!(f, "Hole {index}: {error}"),
+            LevelError::InvalidIsland { index, error } => write!(f, "Island {index}: {error}"),
+            LevelError::InvalidWater { index, error } => write!(f, "Water {index}: {error}"),
+            LevelError::InvalidObstacle { index, error } => {
+                write!(f, "Obstacle {index}: {error}")
+            }
+            LevelError::NoMotion { index } => {
+                write!(
+                    f,
+                    "Obstacle {index} needs a non-zero period and at least one waypoint"
+                )
+            }
+            LevelError::InvalidGadget { index } => {
+                write!(f, "Gadget {index} needs a positive size")
+            }
+            LevelError::InvalidZone { index, error } => write!(f, "Zone {index}: {error}"),
+            LevelError::NoGustPeriod { index } => {
+                write!(f, "Zone {index} gusts but has no period")
+            }
+            LevelError::InvalidPortal { pair } => {
+                write!(f, "Portal pair {pair} needs a positive width")
+            }
+            LevelError::InvalidBreakable { index, error } => {
+                write!(f, "Breakable wall {index}: {error}")
+            }
+            LevelError::PortalOutOfBounds { pair } => {
+                write!(
+                    f,
+                    "Portal pair {pair} is outside the level, the ball would be reset as it came out"
+                )
+            }
+            LevelError::PortalExitInsideTerrain { pair } => {
+                write!(
+                    f,
+                    "Portal pair {pair} faces into the terrain, the ball would come out stuck in it"
+                )
+            }
+            LevelError::StarOutOfBounds { index } => {
+                write!(
+                    f,
+                    "Star {index} is outside the level, the ball couldn't reach it"
+                )
+            }
+            LevelError::StarInsideTerrain { index } => {
+                write!(
+                    f,
+                    "Star {index} is inside the terrain, the ball couldn't reach it"
+                )
+            }
+            LevelError::InvalidCheckpoint { index } => {
+                write!(f, "Checkpoint {index} needs a positive height")
+            }
+            LevelError::InvalidGate { index, error } => write!(f, "Gate {index}: {error}"),
+            LevelError::InvalidTrigger { index, error } => write!(f, "Trigger {index}: {error}"),
+            LevelError::MissingActionTarget { trigger, action } => {
+                write!(
+                    f,
+                    "Action {action} of trigger {trigger} is for a gate or obstacle the level doesn't have"
+                )
+            }
+            LevelError::CheckpointOutOfBounds { index } => {
+                write!(
+                    f,
+                    "Checkpoint {index} is outside the level, the ball would be reset as soon as it got there"
+                )
+            }
+            LevelError::HoleOutsideTerrain { index } => {
+                write!(f, "Hole {index} isn't completely inside the terrain")
+            }
+            LevelError::WaterOutsideLevel { index } => {
+                write!(f, "Water {index} isn't completely over the terrain")
+            }
+            LevelError::WaterInsideTerrain { index } => {
+                write!(
+                    f,
+                    "Water {index} is buried in the terrain, the ball can't reach it"
+                )
+            }
+            LevelError::IslandInsideTerrain { index } => {
+                write!(f, "Island {index} overlaps the terrain")
+            }
+            LevelError::SurfaceOutOfRange { from, to, len } => {
+                write!(
+                    f,
+                    "Surface from {from} to {to} doesn't fit in the outline, which is {len} long"
+                )
+            }
+            LevelError::InvalidColour { colour } => {
+                write!(f, "\"{colour}\" isn't a hex colour")
+            }
+            LevelError::NoNotch => {
+                write!(f, "Goal index doesn't point at the bottom left of a notch")
+            }
+            LevelError::NoLives => {
+                write!(
+                    f,
+                    "Lives has to be at least 1, or left out to use the usual amount"
+                )
+            }
+            LevelError::DuplicateCup { goal_index } => {
+                write!(f, "There's more than one cup at goal index {goal_index}")
+            }
+            LevelError::CupWidthMismatch { width, notch_width } => {
+                write!(f, "Cup is {width} wide but its notch is {notch_width} wide")
+            }
+            LevelError::CupTooDeep { depth, notch_depth } => {
+                write!(
+                    f,
+                    "Cup is {depth} deep but its notch is only {notch_depth} deep"
+                )
+            }
+            LevelError::Triangulation => write!(f, "Could not triangulate the outline"),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+/// Check a level's outlines and where its goals and tees are before we try to build it.
+/// `tees` are the positions the ball can be placed at, in the same space as `points`.
+pub fn validate_level(
+    points: &[Vec2],
+    holes: &[Vec<Vec2>],
+    islands: &[Vec<Vec2>],
+    water: &[Vec<Vec2>],
+    goal_indices: &[usize],
+    tees: &[Vec2],
+) -> Result<(), LevelError> {
+    validate_outline(points)?;
+
+    for (index, hole) in holes.iter().enumerate() {
+        validate_outline(hole).map_err(|error| LevelError::InvalidHole {
+            index,
+            error: Box::new(error),
+        })?;
+
+        // Every corner can be inside while an edge still cuts across the outline
+        if !hole.iter().all(|&p| is_point_inside(points, p)) || outlines_cross(hole, points) {
+            return Err(LevelError::HoleOutsideTerrain { index });
+        }
+    }
+
+    for (index, island) in islands.iter().enumerate() {
+        validate_outline(island).map_err(|error| LevelError::InvalidIsland {
+            index,
+            error: Box::new(error),
+        })?;
+
+        if island.iter().any(|&p| is_point_inside(points, p)) || outlines_cross(island, points) {
+            return Err(LevelError::IslandInsideTerrain { index });
+        }
+    }
+
+    for (index, pool) in water.iter().enumerate() {
+        validate_water(index, pool, points, holes, islands)?;
+    }
+
+    for &goal_index in goal_indices {
+        if goal_index >= points.len() {
+            return Err(LevelError::GoalIndexOutOfRange {
+                index: goal_index,
+                len: points.len(),
+            });
+        }
+    }
+
+    for &tee in tees {
+        if is_inside_terrain(points, holes, islands, tee) {
+            return Err(LevelError::TeeInsideTerrain { tee });
+        }
+    }
+
+    Ok(())
+}
+
+/// Water has to be a proper polygon somewhere the ball can land in it
+pub fn validate_water(
+    index: usize,
+    pool: &[Vec2],
+    points: &[Vec2],
+    holes: &[Vec<Vec2>],
+    islands: &[Vec<Vec2>],
+) -> Result<(), LevelError> {
+    validate_outline(pool).map_err(|error| LevelError::InvalidWater {
+        index,
+        error: Box::new(error),
+    })?;
+
+    if !pool.iter().all(|&p| is_over_terrain(p, points)) {
+        return Err(LevelError::WaterOutsideLevel { index });
+    }
+
+    if pool
+        .iter()
+        .all(|&p| is_inside_terrain(points, holes, islands, p))
+    {
+        return Err(LevelError::WaterInsideTerrain { index });
+    }
+
+    Ok(())
+}
+
+pub fn validate_obstacle(index: usize, points: &[Vec2], motion: &Motion) -> Result<(), LevelError> {
+    validate_outline(points).map_err(|error| LevelError::InvalidObstacle {
+        index,
+        error: Box::new(error),
+    })?;
+
+    let has_waypoints = match motion {
+        Motion::Path { waypoints, .. } => !waypoints.is_empty(),
+        _ => true,
+    };
+
+    if motion.period() == 0.0 || !has_waypoints {
+        return Err(LevelError::NoMotion { index });
+    }
+
+    Ok(())
+}
+
+pub fn validate_zone(index: usize, points: &[Vec2], effect: &ZoneEffect) -> Result<(), LevelError> {
+    validate_outline(points).map_err(|error| LevelError::InvalidZone {
+        index,
+        error: Box::new(error),
+    })?;
+
+    if let ZoneEffect::Wind {
+        gust: Some(gust), ..
+    } = effect
+    {
+        if gust.period == 0.0 {
+            return Err(LevelError::NoGustPeriod { index });
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_breakable(index: usize, points: &[Vec2]) -> Result<(), LevelError> {
+    validate_outline(points).map_err(|error| LevelError::InvalidBreakable {
+        index,
+        error: Box::new(error),
+    })
+}
+
+pub fn validate_gate(index: usize, points: &[Vec2]) -> Result<(), LevelError> {
+    validate_outline(points).map_err(|error| LevelError::InvalidGate {
+        index,
+        error: Box::new(error),
+    })
+}
+
+/// Every gate and obstacle a trigger's actions are for has to be in the level
+pub fn validate_trigger(
+    index: usize,
+    trigger: &LevelTrigger,
+    gate_count: usize,
+    obstacle_count: usize,
+) -> Result<(), LevelError> {
+    validate_outline(&trigger.points).map_err(|error| LevelError::InvalidTrigger {
+        index,
+        error: Box::new(error),
+    })?;
+
+    for (action_index, action) in trigger.actions.iter().enumerate() {
+        let is_missing = match *action {
+            LevelAction::OpenGate(gate) => gate >= gate_count,
+            LevelAction::StartObstacle(obstacle) => obstacle >= obstacle_count,
+            LevelAction::ToggleGravity(_) | LevelAction::PlaySound(_) => false,
+        };
+
+        if is_missing {
+            return Err(LevelError::MissingActionTarget {
+                trigger: index,
+                action: action_index,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Both ends of a pair of portals have to be over the terrain, the camera only shows that much of the level
+pub fn validate_portal(
+    pair: usize,
+    ends: &[LevelPortal; 2],
+    points: &[Vec2],
+) -> Result<(), LevelError> {
+    if ends.iter().any(|end| end.width <= 0.0) {
+        return Err(LevelError::InvalidPortal { pair });
+    }
+
+    if !ends.iter().all(|end| is_over_terrain(end.position, points)) {
+        return Err(LevelError::PortalOutOfBounds { pair });
+    }
+
+    // Where the ball comes out of each end, as `teleport_ball` places it
+    let is_exit_blocked = |end: &LevelPortal| {
+        let up = Rot2::radians(end.angle) * Vec2::Y;
+        let exit = end.position + up * (PORTAL_DEPTH / 2.0 + BALL_RADIUS + EXIT_CLEARANCE);
+        is_point_inside(points, exit)
+    };
+
+    if ends.iter().any(is_exit_blocked) {
+        return Err(LevelError::PortalExitInsideTerrain { pair });
+    }
+
+    Ok(())
+}
+
+/// Stars have to be over the terrain too, or the ball would be reset before it could reach them, and not buried
+/// in it
+pub fn validate_star(index: usize, position: Vec2, points: &[Vec2]) -> Result<(), LevelError> {
+    if !is_over_terrain(position, points) {
+        return Err(LevelError::StarOutOfBounds { index });
+    }
+
+    if is_point_inside(points, position) {
+        return Err(LevelError::StarInsideTerrain { index });
+    }
+
+    Ok(())
+}
+
+pub fn validate_checkpoint(
+    index: usize,
+    checkpoint: &LevelCheckpoint,
+    points: &[Vec2],
+) -> Result<(), LevelError> {
+    if checkpoint.height <= 0.0 {
+        return Err(LevelError::InvalidCheckpoint { index });
+    }
+
+    if !is_over_terrain(checkpoint.position, points) {
+        return Err(LevelError::CheckpointOutOfBounds { index });
+    }
+
+    Ok(())
+}
+
+/// Whether `position` is above the bottom of the outline and between its sides
+fn is_over_terrain(position: Vec2, points: &[Vec2]) -> bool {
+    let bounds = Rect::from_corners(
+        points.iter().copied().reduce(Vec2::min).unwrap_or_default(),
+        points.iter().copied().reduce(Vec2::max).unwrap_or_default(),
+    );
+
+    position.x >= bounds.min.x && position.x <= bounds.max.x && position.y >= bounds.min.y
+}
+
+/// Check that `points` describe a closed, simple polygon
+pub fn validate_outline(points: &[Vec2]) -> Result<(), LevelError> {
+    // A triangle plus the point that closes it
+    if points.len() < 4 {
+        return Err(LevelError::TooFewPoints {
+            count: points.len(),
+        });
+    }
+
+    let gap = points[points.len() - 1] - points[0];
+    if gap.length() > CLOSED_TOLERANCE {
+        return Err(LevelError::NotClosed { gap });
+    }
+
+    let segments: Vec<(Vec2, Vec2)> = points.windows(2).map(|w| (w[0], w[1])).collect();
+
+    if let Some(index) = segments.iter().position(|(start, end)| start == end) {
+        return Err(LevelError::DegenerateSegment { index });
+    }
+
+    let last = segments.len() - 1;
+
+    for (first, &a) in segments.iter().enumerate() {
+        for (second, &b) in segments.iter().enumerate().skip(first + 2) {
+            // The last segment joins back up with the first one
+            if first == 0 && second == last {
+                continue;
+            }
+
+            if segments_intersect(a, b) {
+                return Err(LevelError::SelfIntersecting { first, second });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `point` is somewhere solid, taking caves and floating islands into account
+pub fn is_inside_terrain(
+    points: &[Vec2],
+    holes: &[Vec<Vec2>],
+    islands: &[Vec<Vec2>],
+    point: Vec2,
+) -> bool {
+    let inside_outline =
+        is_point_inside(points, point) && !holes.iter().any(|hole| is_point_inside(hole, point));
+
+    inside_outline || islands.iter().any(|island| is_point_inside(island, point))
+}
+
+/// Even-odd test for whether `point` is inside the polygon made by `points`
+pub fn is_point_inside(points: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+
+    for w in points.windows(2) {
+        let (a, b) = (w[0], w[1]);
+
+        if (a.y > point.y) != (b.y > point.y) {
+            let crossing_x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+
+            if point.x < crossing_x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Whether any edge of one polygon touches or crosses an edge of the other
+fn outlines_cross(a: &[Vec2], b: &[Vec2]) -> bool {
+    a.windows(2).any(|a| {
+        b.windows(2)
+            .any(|b| segments_intersect((a[0], a[1]), (b[0], b[1])))
+    })
+}
+
+fn segments_intersect((a1, a2): (Vec2, Vec2), (b1, b2): (Vec2, Vec2)) -> bool {
+    let d1 = (b2 - b1).perp_dot(a1 - b1);
+    let d2 = (b2 - b1).perp_dot(a2 - b1);
+    let d3 = (a2 - a1).perp_dot(b1 - a1);
+    let d4 = (a2 - a1).perp_dot(b2 - a1);
+
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+
+    (d1 == 0.0 && is_within_bounds(b1, b2, a1))
+        || (d2 == 0.0 && is_within_bounds(b1, b2, a2))
+        || (d3 == 0.0 && is_within_bounds(a1, a2, b1))
+        || (d4 == 0.0 && is_within_bounds(a1, a2, b2))
+}
+
+/// Whether `point`, which is known to be on the line through `start` and `end`, lies between them
+fn is_within_bounds(start: Vec2, end: Vec2, point: Vec2) -> bool {
+    point.cmpge(start.min(end)).all() && point.cmple(start.max(end)).all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10 by 10, with the playing surface along the top
+    const SQUARE: [Vec2; 5] = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(10.0, 0.0),
+        Vec2::new(10.0, -10.0),
+        Vec2::new(0.0, -10.0),
+        Vec2::new(0.0, 0.0),
+    ];
+
+    #[test]
+    fn valid_level_passes() {
+        assert_eq!(validate_outline(&SQUARE), Ok(()));
+        assert_eq!(
+            validate_level(&SQUARE, &[], &[], &[], &[1], &[Vec2::new(5.0, 5.0)]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn too_few_points() {
+        assert_eq!(
+            validate_outline(&SQUARE[..3]),
+            Err(LevelError::TooFewPoints { count: 3 })
+        );
+    }
+
+    #[test]
+    fn not_closed() {
+        assert_eq!(
+            validate_outline(&SQUARE[..4]),
+            Err(LevelError::NotClosed {
+                gap: Vec2::new(0.0, -10.0)
+            })
+        );
+    }
+
+    #[test]
+    fn degenerate_segment() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, -10.0),
+            Vec2::new(0.0, -10.0),
+            Vec2::new(0.0, 0.0),
+        ];
+
+        assert_eq!(
+            validate_outline(&points),
+            Err(LevelError::DegenerateSegment { index: 1 })
+        );
+    }
+
+    #[test]
+    fn bow_tie_is_self_intersecting() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(0.0, 10.0),
+            Vec2::new(0.0, 0.0),
+        ];
+
+        assert_eq!(
+            validate_outline(&points),
+            Err(LevelError::SelfIntersecting {
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    #[test]
+    fn goal_index_out_of_range() {
+        assert_eq!(
+            validate_level(&SQUARE, &[], &[], &[], &[5], &[Vec2::new(5.0, 5.0)]),
+            Err(LevelError::GoalIndexOutOfRange { index: 5, len: 5 })
+        );
+    }
+
+    #[test]
+    fn tee_inside_terrain() {
+        let tee = Vec2::new(5.0, -5.0);
+
+        assert_eq!(
+            validate_level(&SQUARE, &[], &[], &[], &[1], &[tee]),
+            Err(LevelError::TeeInsideTerrain { tee })
+        );
+    }
+}
@@ -5,6 +5,8 @@ use crate::{
     ball::{BallHitEvent, BallStoppedEvent},
     course::CourseState,
     level::LevelState,
+    level_data::CurrentLevel,
+    water::BallInWaterEvent,
 };
 
 pub struct LivesPlugin;
@@ -22,7 +24,7 @@ impl Plugin for LivesPlugin {
             toggle_lives_left_visibility.run_if(state_changed::<AppState>),
         );
 
-        app.add_systems(PostUpdate, react_to_ball_hit);
+        app.add_systems(PostUpdate, (react_to_ball_hit, react_to_ball_in_water));
         app.add_systems(
             Last,
             react_to_ball_stopped.run_if(in_state(LevelState::InPlay)),
@@ -94,12 +96,22 @@ fn react_to_ball_hit(
     mut event_reader: EventReader<BallHitEvent>,
 ) {
     for _ in event_reader.read() {
-        **lives -= 1;
+        **lives = lives.saturating_sub(1);
 
         next_level_state.set(LevelState::InPlay);
     }
 }
 
+/// Landing in water costs a stroke on top of the one used to get there
+fn react_to_ball_in_water(
+    mut lives: ResMut<LivesLeft>,
+    mut event_reader: EventReader<BallInWaterEvent>,
+) {
+    for _ in event_reader.read() {
+        **lives = lives.saturating_sub(1);
+    }
+}
+
 fn react_to_ball_stopped(
     lives: Res<LivesLeft>,
     mut next_level_state: ResMut<NextState<LevelState>>,
@@ -116,6 +128,13 @@ fn react_to_ball_stopped(
     }
 }
 
-fn reset_lives_left(mut lives_left: ResMut<LivesLeft>, lives: Res<Lives>) {
-    **lives_left = **lives;
+fn reset_lives_left(
+    mut lives_left: ResMut<LivesLeft>,
+    lives: Res<Lives>,
+    current_level: CurrentLevel,
+) {
+    **lives_left = current_level
+        .get()
+        .and_then(|level| level.lives)
+        .unwrap_or(**lives);
 }
@@ -1,43 +1,95 @@
 mod app;
 mod background;
 mod ball;
+mod breakable;
 mod cam;
+mod checkpoint;
 mod course;
+mod course_data;
+mod cup;
 mod debug;
+mod direction;
+mod gadget;
+mod generator;
+mod hud;
 mod level;
 mod level_data;
+mod level_validation;
 mod lives;
 mod menu;
 mod mouse;
 mod music;
+mod obstacle;
 mod physics;
+mod portal;
+mod solver;
 mod sounds;
+mod star;
+mod surface;
+mod svg_import;
 mod swing;
+mod tee;
+mod terrain;
+mod thumbnail;
+mod trigger;
+mod water;
+mod zone;
 
 use app::AppPlugin;
 use background::BackgroundPlugin;
 use ball::BallPlugin;
 use bevy::{asset::AssetMetaCheck, prelude::*};
-use cam::CamPlugin;
+use breakable::BreakablePlugin;
+use cam::{CamPlugin, WINDOW_SIZE};
+use checkpoint::CheckpointPlugin;
 use course::CoursePlugin;
+use course_data::CourseDataPlugin;
 use debug::DebugPlugin;
+use gadget::GadgetPlugin;
+use generator::GeneratorPlugin;
+use hud::HudPlugin;
 use level::LevelPlugin;
 use level_data::LevelDataPlugin;
 use lives::LivesPlugin;
 use menu::MenuPlugin;
 use mouse::MousePlugin;
 use music::MusicPlugin;
+use obstacle::ObstaclePlugin;
 use physics::PhysicsPlugin;
+use portal::PortalPlugin;
 use sounds::SoundPlugin;
+use star::StarPlugin;
+use svg_import::SvgImportPlugin;
 use swing::SwingPlugin;
+use tee::TeePlugin;
+use trigger::TriggerPlugin;
+use water::WaterPlugin;
+use zone::ZonePlugin;
 
 fn main() {
+    // `golf --thumbnails <output folder> <level files>...` draws levels without opening a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [flag, output, level_paths @ ..] = args.as_slice() {
+        if flag == "--thumbnails" {
+            thumbnail::export_thumbnails(std::path::Path::new(output), level_paths);
+            return;
+        }
+    }
+
+    // `golf --solve <level files>...` plays each level headlessly and reports the fewest strokes it found
+    if let [flag, level_paths @ ..] = args.as_slice() {
+        if flag == "--solve" {
+            solver::solve_levels(level_paths);
+            return;
+        }
+    }
+
     App::new()
         .add_plugins(
             DefaultPlugins
                 .set(WindowPlugin {
                     primary_window: Some(Window {
-                        resolution: (720.0, 1216.0).into(),
+                        resolution: (WINDOW_SIZE.x, WINDOW_SIZE.y).into(),
                         decorations: false,
                         title: "Golf".to_string(),
                         canvas: Some("#golf-canvas".into()),
@@ -55,16 +107,30 @@ fn main() {
         .add_plugins(DebugPlugin)
         .add_plugins(AppPlugin)
         .add_plugins(PhysicsPlugin)
+        .add_plugins(CourseDataPlugin)
         .add_plugins(LevelDataPlugin)
+        .add_plugins(SvgImportPlugin)
+        .add_plugins(GeneratorPlugin)
+        .add_plugins(TeePlugin)
         .add_plugins(MenuPlugin)
         .add_plugins(CoursePlugin)
         .add_plugins(LevelPlugin)
         .add_plugins(BallPlugin)
         .add_plugins(LivesPlugin)
+        .add_plugins(HudPlugin)
         .add_plugins(MusicPlugin)
         .add_plugins(BackgroundPlugin)
         .add_plugins(SoundPlugin)
         .add_plugins(MousePlugin)
         .add_plugins(SwingPlugin)
+        .add_plugins(WaterPlugin)
+        .add_plugins(ObstaclePlugin)
+        .add_plugins(GadgetPlugin)
+        .add_plugins(ZonePlugin)
+        .add_plugins(PortalPlugin)
+        .add_plugins(BreakablePlugin)
+        .add_plugins(StarPlugin)
+        .add_plugins(CheckpointPlugin)
+        .add_plugins(TriggerPlugin)
         .run();
 }
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::app::AppState;
+use crate::{app::AppState, course::CurrentCourse, course_data::Courses, tee::TeeChoice};
 
 pub struct MenuPlugin;
 
@@ -8,14 +8,25 @@ impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(AppState::Menu), spawn_menu);
 
-        app.add_systems(Update, menu.run_if(in_state(AppState::Menu)));
+        app.add_systems(
+            Update,
+            (menu, change_tee_choice).run_if(in_state(AppState::Menu)),
+        );
     }
 }
 
 #[derive(Component)]
 struct MainMenu;
 
-fn spawn_menu(mut commands: Commands) {
+/// Starts the course at this index into `Courses` when pressed
+#[derive(Component)]
+struct CourseButton(usize);
+
+/// Goes through the tee choices when pressed
+#[derive(Component)]
+struct TeeChoiceButton;
+
+fn spawn_menu(mut commands: Commands, courses: Res<Courses>, tee_choice: Res<TeeChoice>) {
     commands
         .spawn((
             MainMenu,
@@ -23,22 +34,63 @@ fn spawn_menu(mut commands: Commands) {
             Node {
                 width: Val::Percent(100.),
                 height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
+                row_gap: Val::Px(24.0),
                 ..default()
             },
         ))
         .with_children(|parent| {
-            parent.spawn((
-                Text::new("PLAY"),
-                TextFont::from_font_size(64.0),
-                TextColor::WHITE,
-            ));
+            for (index, course) in courses.iter().enumerate() {
+                parent
+                    .spawn((CourseButton(index), Button, Node::default()))
+                    .with_child((
+                        Text::new(course.name.to_uppercase()),
+                        TextFont::from_font_size(64.0),
+                        TextColor::WHITE,
+                    ));
+            }
+
+            parent
+                .spawn((TeeChoiceButton, Button, Node::default()))
+                .with_child((
+                    Text::new(tee_choice.label()),
+                    TextFont::from_font_size(32.0),
+                    TextColor::WHITE,
+                ));
         });
 }
 
-fn menu(input: Res<ButtonInput<MouseButton>>, mut next_state: ResMut<NextState<AppState>>) {
-    if input.just_released(MouseButton::Left) {
-        next_state.set(AppState::InGame);
+fn menu(
+    button_q: Query<(&Interaction, &CourseButton), Changed<Interaction>>,
+    mut current_course: ResMut<CurrentCourse>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, course_button) in &button_q {
+        if *interaction == Interaction::Pressed {
+            **current_course = course_button.0;
+            next_state.set(AppState::InGame);
+        }
+    }
+}
+
+fn change_tee_choice(
+    button_q: Query<(&Interaction, &Children), (Changed<Interaction>, With<TeeChoiceButton>)>,
+    mut text_q: Query<&mut Text>,
+    mut tee_choice: ResMut<TeeChoice>,
+) {
+    for (interaction, children) in &button_q {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        *tee_choice = tee_choice.next();
+
+        for &child in children {
+            if let Ok(mut text) = text_q.get_mut(child) {
+                text.0 = tee_choice.label().to_string();
+            }
+        }
     }
 }
@@ -1,18 +1,44 @@
 use bevy::{audio::Volume, prelude::*};
 
+use crate::{course::CurrentCourse, course_data::Courses};
+
 pub struct MusicPlugin;
 
 impl Plugin for MusicPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup);
+        app.add_systems(
+            Update,
+            play_course_music
+                .run_if(resource_changed::<CurrentCourse>.or(resource_changed::<Courses>)),
+        );
     }
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let background_music = asset_server.load::<AudioSource>("songs/otherside.ogg");
+#[derive(Component)]
+struct BackgroundMusic;
+
+fn play_course_music(
+    mut commands: Commands,
+    courses: Res<Courses>,
+    current_course: Res<CurrentCourse>,
+    music_q: Query<(Entity, &AudioPlayer), With<BackgroundMusic>>,
+) {
+    let Some(course) = courses.get(**current_course) else {
+        return;
+    };
+
+    // Keep playing from where it is if the next course uses the same song
+    if music_q.iter().any(|(_, player)| player.0 == course.music) {
+        return;
+    }
+
+    for (entity, _) in &music_q {
+        commands.entity(entity).despawn();
+    }
 
     commands.spawn((
-        AudioPlayer(background_music),
+        BackgroundMusic,
+        AudioPlayer(course.music.clone()),
         PlaybackSettings {
             mode: bevy::audio::PlaybackMode::Loop,
             volume: Volume::new(0.2),
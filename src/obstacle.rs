@@ -0,0 +1,192 @@
+use std::f32::consts::{PI, TAU};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    level::LevelElement,
+    level_data::{polygon_collider, LevelObstacle},
+    physics::PhysicsState,
+    surface::Surface,
+};
+
+pub struct ObstaclePlugin;
+
+impl Plugin for ObstaclePlugin {
+    fn build(&self, app: &mut App) {
+        // Runs just before the physics step so obstacles move in lockstep with it
+        app.add_systems(
+            FixedUpdate,
+            move_obstacles.run_if(in_state(PhysicsState::Running)),
+        );
+    }
+}
+
+/// How an obstacle moves. Every motion repeats each `period` seconds and `phase` is how far through a period it starts, from 0 to 1
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Motion {
+    /// Slides through `waypoints` at a constant speed, looping back to the first one.
+    /// Waypoints are relative to the obstacle's position.
+    Path {
+        waypoints: Vec<Vec2>,
+        period: f32,
+        #[serde(default)]
+        phase: f32,
+    },
+    /// Turns a full circle each period, anticlockwise when the period is positive
+    Spin {
+        period: f32,
+        #[serde(default)]
+        phase: f32,
+    },
+    /// Swings `amplitude` degrees either side of where it started
+    Pendulum {
+        amplitude: f32,
+        period: f32,
+        #[serde(default)]
+        phase: f32,
+    },
+}
+
+impl Motion {
+    pub fn period(&self) -> f32 {
+        match *self {
+            Motion::Path { period, .. }
+            | Motion::Spin { period, .. }
+            | Motion::Pendulum { period, .. } => period,
+        }
+    }
+
+    /// Offset from the obstacle's position and its rotation in radians, `elapsed` seconds after the level started
+    pub fn pose(&self, elapsed: f32) -> (Vec2, f32) {
+        match self {
+            Motion::Path {
+                waypoints,
+                period,
+                phase,
+            } => {
+                let (Some(&first), Some(&last)) = (waypoints.first(), waypoints.last()) else {
+                    return (Vec2::ZERO, 0.0);
+                };
+
+                let segments: Vec<(Vec2, Vec2)> = waypoints
+                    .windows(2)
+                    .map(|w| (w[0], w[1]))
+                    .chain([(last, first)])
+                    .collect();
+
+                let total_length: f32 = segments.iter().map(|(a, b)| a.distance(*b)).sum();
+
+                if total_length <= 0.0 {
+                    return (first, 0.0);
+                }
+
+                let mut distance = (elapsed / period + phase).rem_euclid(1.0) * total_length;
+
+                for (a, b) in segments {
+                    let length = a.distance(b);
+
+                    if distance <= length {
+                        return (a.lerp(b, distance / length), 0.0);
+                    }
+
+                    distance -= length;
+                }
+
+                (first, 0.0)
+            }
+            Motion::Spin { period, phase } => (Vec2::ZERO, TAU * (elapsed / period + phase)),
+            Motion::Pendulum {
+                amplitude,
+                period,
+                phase,
+            } => (
+                Vec2::ZERO,
+                amplitude.to_radians() * (TAU * (elapsed / period + phase)).sin(),
+            ),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Obstacle {
+    /// Index into the level's `obstacles`
+    pub index: usize,
+    /// Waiting obstacles stay where they start until a trigger sets them going
+    pub running: bool,
+    pub origin: Vec2,
+    pub motion: Motion,
+    /// Seconds of physics time since the obstacle was spawned
+    pub elapsed: f32,
+}
+
+pub fn obstacle_bundle(
+    obstacle: &LevelObstacle,
+    index: usize,
+    material: Handle<ColorMaterial>,
+) -> impl Bundle {
+    let (offset, angle) = obstacle.motion.pose(0.0);
+    let position = obstacle.position + offset;
+
+    (
+        Name::new("Obstacle"),
+        LevelElement,
+        Obstacle {
+            index,
+            running: !obstacle.waiting,
+            origin: obstacle.position,
+            motion: obstacle.motion.clone(),
+            elapsed: 0.0,
+        },
+        Mesh2d(obstacle.mesh.clone()),
+        MeshMaterial2d(material),
+        polygon_collider(&obstacle.points).unwrap_or_default(),
+        RigidBody::Kinematic,
+        Position(position),
+        Rotation::radians(angle),
+        Transform::from_translation(position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(angle)),
+        Surface::Fairway.friction(),
+        Surface::Fairway.restitution(),
+    )
+}
+
+/// Put each obstacle exactly where its motion says it should be, and give it the velocity that gets it to the
+/// next pose so the ball gets pushed along properly. Only depending on elapsed fixed time keeps this deterministic.
+fn move_obstacles(
+    mut obstacles_q: Query<(
+        &mut Obstacle,
+        &mut Position,
+        &mut Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    if dt <= 0.0 {
+        return;
+    }
+
+    for (mut obstacle, mut pos, mut rot, mut vel, mut angular_vel) in &mut obstacles_q {
+        if !obstacle.running {
+            vel.0 = Vec2::ZERO;
+            angular_vel.0 = 0.0;
+            continue;
+        }
+
+        let (offset, angle) = obstacle.motion.pose(obstacle.elapsed);
+
+        obstacle.elapsed += dt;
+
+        let (next_offset, next_angle) = obstacle.motion.pose(obstacle.elapsed);
+
+        pos.0 = obstacle.origin + offset;
+        *rot = Rotation::radians(angle);
+        vel.0 = (next_offset - offset) / dt;
+        angular_vel.0 = ((next_angle - angle + PI).rem_euclid(TAU) - PI) / dt;
+    }
+}
@@ -1,5 +1,6 @@
 use avian2d::{math::Vector, prelude::*};
 use bevy::prelude::*;
+use serde::Deserialize;
 
 pub struct PhysicsPlugin;
 
@@ -7,12 +8,9 @@ impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(PhysicsPlugins::default())
             .init_state::<PhysicsState>()
-            .insert_resource(SleepingThreshold {
-                angular: 3.0,
-                linear: 3.0,
-            })
-            .insert_resource(Gravity(Vector::NEG_Y * 9.81 * 80.0))
-            .insert_resource(Time::<Fixed>::from_hz(640.0))
+            .insert_resource(LevelPhysics::default().sleeping_threshold())
+            .insert_resource(LevelPhysics::default().gravity())
+            .insert_resource(Time::<Fixed>::from_hz(PHYSICS_HZ))
             .add_systems(
                 OnEnter(PhysicsState::Paused),
                 |mut time: ResMut<Time<Physics>>| time.pause(),
@@ -25,6 +23,98 @@ impl Plugin for PhysicsPlugin {
     }
 }
 
+/// Physics steps each second, obstacles, zones and boost pads move things along at the same rate
+pub const PHYSICS_HZ: f64 = 640.0;
+
+const GRAVITY_STRENGTH: f32 = 9.81 * 80.0;
+const SLEEPING_THRESHOLD: f32 = 3.0;
+const BALL_LINEAR_DAMPING: f32 = 0.0;
+const BALL_ANGULAR_DAMPING: f32 = 8.0;
+
+/// Physics settings a level can change, anything it leaves out is the same as every other level
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+pub struct RawLevelPhysics {
+    /// Degrees anticlockwise from straight down, 90 makes things fall to the right
+    pub gravity_angle: Option<f32>,
+    /// How strong gravity is compared to usual, the moon is about 0.17
+    pub gravity_scale: Option<f32>,
+    /// How slowly the ball has to be moving before it's considered stopped
+    pub sleeping_linear: Option<f32>,
+    pub sleeping_angular: Option<f32>,
+    /// How quickly the ball slows down on its own, through the air as well as on the ground
+    pub ball_linear_damping: Option<f32>,
+    pub ball_angular_damping: Option<f32>,
+}
+
+/// Physics settings for one level, applied when it's loaded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelPhysics {
+    pub gravity_angle: f32,
+    pub gravity_scale: f32,
+    pub sleeping_linear: f32,
+    pub sleeping_angular: f32,
+    pub ball_linear_damping: f32,
+    pub ball_angular_damping: f32,
+}
+
+impl Default for LevelPhysics {
+    fn default() -> Self {
+        Self {
+            gravity_angle: 0.0,
+            gravity_scale: 1.0,
+            sleeping_linear: SLEEPING_THRESHOLD,
+            sleeping_angular: SLEEPING_THRESHOLD,
+            ball_linear_damping: BALL_LINEAR_DAMPING,
+            ball_angular_damping: BALL_ANGULAR_DAMPING,
+        }
+    }
+}
+
+impl RawLevelPhysics {
+    pub fn resolve(self) -> LevelPhysics {
+        let default = LevelPhysics::default();
+
+        LevelPhysics {
+            gravity_angle: self.gravity_angle.unwrap_or(default.gravity_angle),
+            gravity_scale: self.gravity_scale.unwrap_or(default.gravity_scale),
+            sleeping_linear: self.sleeping_linear.unwrap_or(default.sleeping_linear),
+            sleeping_angular: self.sleeping_angular.unwrap_or(default.sleeping_angular),
+            ball_linear_damping: self
+                .ball_linear_damping
+                .unwrap_or(default.ball_linear_damping),
+            ball_angular_damping: self
+                .ball_angular_damping
+                .unwrap_or(default.ball_angular_damping),
+        }
+    }
+}
+
+impl LevelPhysics {
+    pub fn gravity(&self) -> Gravity {
+        gravity_towards(self.gravity_angle, self.gravity_scale)
+    }
+
+    pub fn sleeping_threshold(&self) -> SleepingThreshold {
+        SleepingThreshold {
+            linear: self.sleeping_linear,
+            angular: self.sleeping_angular,
+        }
+    }
+
+    pub fn ball_damping(&self) -> (LinearDamping, AngularDamping) {
+        (
+            LinearDamping(self.ball_linear_damping),
+            AngularDamping(self.ball_angular_damping),
+        )
+    }
+}
+
+/// Gravity `angle` degrees anticlockwise from straight down, `scale` times as strong as usual
+pub fn gravity_towards(angle: f32, scale: f32) -> Gravity {
+    let down = Rot2::degrees(angle) * Vector::NEG_Y;
+    Gravity(down * GRAVITY_STRENGTH * scale)
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, States, Default)]
 pub enum PhysicsState {
     #[default]
@@ -0,0 +1,128 @@
+use std::f32::consts::PI;
+
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{FUCHSIA_500, LIME_400, ORANGE_500, SKY_400},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    ball::Ball,
+    level::{LevelElement, BALL_RADIUS},
+    level_data::LevelPortal,
+};
+
+pub struct PortalPlugin;
+
+impl Plugin for PortalPlugin {
+    fn build(&self, app: &mut App) {
+        // Alongside breaking walls, as soon as each physics step finds the ball touching something
+        app.add_systems(PostProcessCollisions, teleport_ball);
+    }
+}
+
+const DEFAULT_PORTAL_WIDTH: f32 = 40.0;
+pub const PORTAL_DEPTH: f32 = 6.0;
+/// Gap left between the ball and the exit portal so it doesn't go straight back in
+pub const EXIT_CLEARANCE: f32 = 2.0;
+
+/// Both ends of a pair are drawn in the same colour, pairs after the last colour start again from the first
+const PORTAL_COLOURS: [Srgba; 4] = [ORANGE_500, SKY_400, LIME_400, FUCHSIA_500];
+
+/// One end of a pair of portals as it's written in a level file
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RawPortal {
+    /// Centre of the portal, from the same origin as the level's outline
+    pub position: Vec2,
+    /// Degrees anticlockwise, 0 faces up. The ball comes out of a portal the way it faces.
+    #[serde(default)]
+    pub angle: f32,
+    #[serde(default = "default_portal_width")]
+    pub width: f32,
+}
+
+fn default_portal_width() -> f32 {
+    DEFAULT_PORTAL_WIDTH
+}
+
+pub fn portal_colour(pair: usize) -> Color {
+    PORTAL_COLOURS[pair % PORTAL_COLOURS.len()].into()
+}
+
+pub fn portal_mesh(width: f32) -> Mesh {
+    Rectangle::new(width, PORTAL_DEPTH).into()
+}
+
+#[derive(Component)]
+pub struct Portal {
+    /// Index into the level's `portals`
+    pub pair: usize,
+    /// 0 or 1, which end of the pair this is
+    pub end: usize,
+}
+
+/// Spawned as a child of the floor, the ball passes into it rather than bouncing off
+pub fn portal_bundle(portal: &LevelPortal, pair: usize, end: usize) -> impl Bundle {
+    (
+        Name::new(format!("Portal {pair}-{end}")),
+        LevelElement,
+        Portal { pair, end },
+        Mesh2d(portal.mesh.clone()),
+        MeshMaterial2d(portal.material.clone()),
+        Collider::rectangle(portal.width, PORTAL_DEPTH),
+        Sensor,
+        // In front of the terrain and its overlay
+        Transform::from_translation(portal.position.extend(0.02))
+            .with_rotation(Quat::from_rotation_z(portal.angle)),
+    )
+}
+
+/// Move the ball out of the other end of a portal it's gone into, turning its velocity with it.
+/// This happens inside the physics step that finds the ball touching the entrance, before the solver moves it any
+/// further, so it never carries on into whatever is behind the portal.
+fn teleport_ball(
+    mut ball_q: Query<(Entity, &mut Position, &mut LinearVelocity), With<Ball>>,
+    portal_q: Query<(&Portal, &GlobalTransform)>,
+    mut collisions: ResMut<Collisions>,
+) {
+    let Ok((ball_entity, mut pos, mut vel)) = ball_q.get_single_mut() else {
+        return;
+    };
+
+    let entrance = collisions
+        .collisions_with_entity(ball_entity)
+        .find_map(|contacts| {
+            let other_entity = if contacts.entity1 == ball_entity {
+                contacts.entity2
+            } else {
+                contacts.entity1
+            };
+
+            portal_q.get(other_entity).ok()
+        });
+
+    let Some((entrance, entrance_transform)) = entrance else {
+        return;
+    };
+
+    let Some((_, exit_transform)) = portal_q
+        .iter()
+        .find(|(portal, _)| portal.pair == entrance.pair && portal.end != entrance.end)
+    else {
+        return;
+    };
+
+    // Going into the entrance against the way it faces comes out of the exit the way it faces
+    let entrance_angle = entrance_transform.up().truncate().to_angle();
+    let exit_up = exit_transform.up().truncate();
+    let turn = Rot2::radians(exit_up.to_angle() - entrance_angle + PI);
+
+    pos.0 = exit_transform.translation().truncate()
+        + exit_up * (PORTAL_DEPTH / 2.0 + BALL_RADIUS + EXIT_CLEARANCE);
+    vel.0 = turn * vel.0;
+
+    // Everything else the ball was touching is back at the entrance
+    collisions
+        .retain(|contacts| contacts.entity1 != ball_entity && contacts.entity2 != ball_entity);
+}
@@ -0,0 +1,557 @@
+use std::{f32::consts::TAU, path::Path, time::Duration};
+
+use avian2d::prelude::*;
+use bevy::{
+    ecs::system::RunSystemOnce, hierarchy::HierarchyPlugin, prelude::*, state::app::StatesPlugin,
+    time::TimeUpdateStrategy, transform::TransformPlugin,
+};
+
+use crate::{
+    ball::BallResetEvent,
+    breakable::{breakable_bundle, restore_walls, BreakablePlugin, BreakableWall, Broken},
+    cam::{zoom_to_fit, WINDOW_SIZE},
+    checkpoint::ReachedCheckpoint,
+    course::{CurrentCourse, NextLevelIndex},
+    course_data::{Course, Courses, Theme},
+    gadget::{spawn_gadget, GadgetPlugin},
+    level::{ball_body, Goal, LevelState},
+    level_data::Level,
+    obstacle::{obstacle_bundle, Obstacle, ObstaclePlugin},
+    physics::{PhysicsState, PHYSICS_HZ},
+    portal::{portal_bundle, PortalPlugin},
+    swing::{Swing, CHAIN_LENGTH},
+    thumbnail::read_level,
+    trigger::{
+        gate_bundle, reset_triggers, spawn_trigger, ActionTrigger, Gate, Open, Spent, TriggerPlugin,
+    },
+    water::Water,
+    zone::{zone_bundle, ForceZone, ZonePlugin},
+};
+
+/// Most strokes looked for before a level is reported as unwinnable
+const MAX_STROKES: u32 = 6;
+/// Angles tried at each power, spread evenly around a full circle
+const ANGLE_STEPS: u32 = 36;
+/// Shots from each place that went nearest a cup, tried again with the aim nudged either side
+const REFINED_SHOTS: usize = 4;
+/// Nudges tried each way, splitting the gap to the next of the `ANGLE_STEPS` evenly
+const REFINE_STEPS: u32 = 4;
+/// Places the ball stops closer together than this count as the same place
+const SAME_PLACE_DISTANCE: f32 = 10.0;
+/// Most places carried on to the next stroke, the ones nearest a cup are kept
+const MAX_PLACES_PER_STROKE: usize = 16;
+/// Simulated seconds a shot gets to finish before it's given up on
+const SHOT_TIMEOUT: f32 = 20.0;
+/// Simulated seconds between checks on the ball, physics itself steps at `PHYSICS_HZ`
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// Try a spread of swings from each level's regular tee and print how few strokes it takes to hole out. Only so
+/// many angles are tried, finer around the shots that come closest, so this is an estimate: a hole that needs
+/// more exact aim than that can take the solver more strokes than it takes a player.
+pub fn solve_levels(level_paths: &[String]) {
+    for level_path in level_paths {
+        let level_path = Path::new(level_path);
+
+        let level = match read_level(level_path) {
+            Ok(level) => level,
+            Err(err) => {
+                eprintln!("Skipping {}: {err}", level_path.display());
+                continue;
+            }
+        };
+
+        let name = level.name.clone();
+        let par = level.par;
+
+        match fewest_strokes(level) {
+            Some(strokes) => println!(
+                "{name} ({}): holed in {strokes}, suggested par {}, par is {par}",
+                level_path.display(),
+                suggested_par(strokes),
+            ),
+            None => println!(
+                "{name} ({}): no way into a cup found within {MAX_STROKES} strokes",
+                level_path.display()
+            ),
+        }
+    }
+}
+
+/// Every swing is perfectly aimed here, players need a stroke more to get close
+fn suggested_par(strokes: u32) -> u32 {
+    strokes + 1
+}
+
+/// Every swing the player can make, at each power level and a spread of angles
+fn swings() -> impl Iterator<Item = Swing> {
+    (1..=CHAIN_LENGTH).flat_map(|power| {
+        (0..ANGLE_STEPS).map(move |step| Swing {
+            power,
+            angle: TAU * step as f32 / ANGLE_STEPS as f32,
+        })
+    })
+}
+
+/// The same power as `swing`, aimed a little either side of it
+fn refined_swings(swing: &Swing) -> impl Iterator<Item = Swing> {
+    let power = swing.power;
+    let angle = swing.angle;
+    let nudge = TAU / (ANGLE_STEPS * (REFINE_STEPS + 1)) as f32;
+
+    (1..=REFINE_STEPS).flat_map(move |step| {
+        [-1.0, 1.0].map(|side| Swing {
+            power,
+            angle: angle + side * nudge * step as f32,
+        })
+    })
+}
+
+/// Search stroke by stroke from the regular tee, only going on from places the ball hasn't already stopped at
+/// with the level the same way
+pub fn fewest_strokes(level: Level) -> Option<u32> {
+    let tee = level.tees.first()?.position;
+
+    let mut simulation = Simulation::new(level);
+    let mut places = vec![Place {
+        position: tee,
+        changes: simulation.changes(),
+    }];
+    let mut visited = places.clone();
+
+    for strokes in 1..=MAX_STROKES {
+        let mut next_places = Vec::new();
+
+        for from in &places {
+            let mut shots: Vec<(Swing, Shot)> = swings()
+                .map(|swing| {
+                    let shot = simulation.shoot(from, &swing);
+                    (swing, shot)
+                })
+                .collect();
+
+            shots.sort_by(|(_, a), (_, b)| a.closest.total_cmp(&b.closest));
+
+            let refined: Vec<Shot> = shots
+                .iter()
+                .take(REFINED_SHOTS)
+                .flat_map(|(swing, _)| refined_swings(swing))
+                .map(|swing| simulation.shoot(from, &swing))
+                .collect();
+
+            for shot in shots.into_iter().map(|(_, shot)| shot).chain(refined) {
+                match shot.outcome {
+                    ShotOutcome::Holed => return Some(strokes),
+                    ShotOutcome::Stopped(at) => {
+                        if !visited.iter().any(|place| place.is_same(&at)) {
+                            visited.push(at.clone());
+                            next_places.push(at);
+                        }
+                    }
+                    ShotOutcome::Lost => {}
+                }
+            }
+        }
+
+        if next_places.is_empty() {
+            return None;
+        }
+
+        next_places.sort_by(|a, b| {
+            simulation
+                .distance_to_cup(a.position)
+                .total_cmp(&simulation.distance_to_cup(b.position))
+        });
+        next_places.truncate(MAX_PLACES_PER_STROKE);
+
+        places = next_places;
+    }
+
+    None
+}
+
+/// Everything a stroke can change that stays changed for the rest of the hole
+#[derive(Clone, PartialEq)]
+struct LevelChanges {
+    open_gates: Vec<Entity>,
+    spent_triggers: Vec<Entity>,
+    broken_walls: Vec<Entity>,
+    running_obstacles: Vec<Entity>,
+    gravity: Vec2,
+}
+
+/// Where the ball came to rest, and how the strokes that got it there left the level
+#[derive(Clone)]
+struct Place {
+    position: Vec2,
+    changes: LevelChanges,
+}
+
+impl Place {
+    fn is_same(&self, other: &Place) -> bool {
+        self.position.distance(other.position) < SAME_PLACE_DISTANCE
+            && self.changes == other.changes
+    }
+}
+
+enum ShotOutcome {
+    Holed,
+    Stopped(Place),
+    /// Out of bounds, in the water, or still going when time ran out
+    Lost,
+}
+
+struct Shot {
+    outcome: ShotOutcome,
+    /// Nearest the ball got to a cup on the way
+    closest: f32,
+}
+
+/// A level on its own in a world with no window, stepped by hand
+struct Simulation {
+    app: App,
+    ball: Entity,
+    /// Leaving these sides or the bottom counts as out of bounds, as it does in the game
+    bounds: Rect,
+    cups: Vec<Vec2>,
+    sensors_q: QueryState<(&'static CollidingEntities, Has<Goal>), Or<(With<Goal>, With<Water>)>>,
+}
+
+impl Simulation {
+    fn new(level: Level) -> Self {
+        let mut app = App::new();
+
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+            StatesPlugin,
+            PhysicsPlugins::default(),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_asset::<Level>()
+        .init_state::<PhysicsState>()
+        // Triggers only go off while the ball's in play, which it always is here
+        .insert_state(LevelState::InPlay)
+        .add_event::<BallResetEvent>()
+        .init_resource::<ReachedCheckpoint>()
+        .init_resource::<CurrentCourse>()
+        .init_resource::<NextLevelIndex>()
+        .add_plugins((
+            ObstaclePlugin,
+            GadgetPlugin,
+            ZonePlugin,
+            PortalPlugin,
+            TriggerPlugin,
+            BreakablePlugin,
+        ))
+        .insert_resource(Time::<Fixed>::from_hz(PHYSICS_HZ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            FRAME_TIME,
+        )))
+        .insert_resource(level.physics.gravity())
+        .insert_resource(level.physics.sleeping_threshold());
+
+        let world = app.world_mut();
+        let mut commands = world.commands();
+
+        // The same as `load_level`, less anything that's only there to be looked at
+        commands
+            .spawn((RigidBody::Static, Transform::default()))
+            .with_children(|parent| {
+                for (surface, collider) in level.surface_colliders() {
+                    parent.spawn((
+                        surface,
+                        collider,
+                        surface.friction(),
+                        surface.restitution(),
+                        Transform::default(),
+                    ));
+                }
+
+                for (index, level_cup) in level.cups.iter().enumerate() {
+                    parent.spawn((
+                        Goal {
+                            index,
+                            points: level_cup.cup.points,
+                        },
+                        level_cup.cup.collider(),
+                        Sensor,
+                        CollidingEntities::default(),
+                        Transform::from_translation(level_cup.sensor_position().extend(0.0)),
+                    ));
+                }
+
+                for zone in &level.zones {
+                    parent.spawn(zone_bundle(zone));
+                }
+
+                for (pair, ends) in level.portals.iter().enumerate() {
+                    for (end, portal) in ends.iter().enumerate() {
+                        parent.spawn(portal_bundle(portal, pair, end));
+                    }
+                }
+
+                for breakable in &level.breakables {
+                    parent.spawn(breakable_bundle(breakable));
+                }
+
+                for (index, gate) in level.gates.iter().enumerate() {
+                    parent.spawn(gate_bundle(gate, index));
+                }
+
+                for (index, trigger) in level.triggers.iter().enumerate() {
+                    spawn_trigger(parent, trigger, index);
+                }
+
+                for gadget in &level.gadgets {
+                    spawn_gadget(parent, gadget);
+                }
+
+                for collider in level.water_colliders() {
+                    parent.spawn((
+                        Water,
+                        collider,
+                        Sensor,
+                        CollidingEntities::default(),
+                        Transform::default(),
+                    ));
+                }
+            });
+
+        for (index, obstacle) in level.obstacles.iter().enumerate() {
+            commands.spawn(obstacle_bundle(obstacle, index, Handle::default()));
+        }
+
+        world.flush();
+
+        let ball = world
+            .spawn(ball_body())
+            .insert(level.physics.ball_damping())
+            .id();
+
+        let sensors_q = world.query_filtered();
+
+        // The same area `oob_check` goes by, what the camera shows once it's zoomed to fit the level
+        let level_min = level
+            .points
+            .iter()
+            .copied()
+            .reduce(Vec2::min)
+            .unwrap_or_default();
+        let level_max = level
+            .points
+            .iter()
+            .copied()
+            .reduce(Vec2::max)
+            .unwrap_or_default();
+        let gate_height = level
+            .checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.position.y + checkpoint.height - level_min.y)
+            .fold(0.0, f32::max);
+        let window = Rect::from_center_size(Vec2::ZERO, WINDOW_SIZE);
+        let bounds = Rect::from_center_size(
+            Vec2::ZERO,
+            WINDOW_SIZE * zoom_to_fit(window, level_max.x - level_min.x, gate_height),
+        );
+
+        let cups = level
+            .cups
+            .iter()
+            .map(|level_cup| level_cup.sensor_position())
+            .collect();
+
+        // Triggers toggling gravity look up the level's own through the current level
+        let level = world.resource_mut::<Assets<Level>>().add(level);
+        world.insert_resource(Courses(vec![Course {
+            name: String::new(),
+            levels: vec![level],
+            theme: Theme {
+                background: Handle::default(),
+            },
+            music: Handle::default(),
+        }]));
+
+        Self {
+            app,
+            ball,
+            bounds,
+            cups,
+            sensors_q,
+        }
+    }
+
+    fn distance_to_cup(&self, position: Vec2) -> f32 {
+        self.cups
+            .iter()
+            .map(|cup| cup.distance(position))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// How the level is right now, sorted so the same changes always compare equal
+    fn changes(&mut self) -> LevelChanges {
+        let world = self.app.world_mut();
+
+        fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+            entities.sort();
+            entities
+        }
+
+        LevelChanges {
+            open_gates: sorted(
+                world
+                    .query_filtered::<Entity, (With<Gate>, With<Open>)>()
+                    .iter(world)
+                    .collect(),
+            ),
+            spent_triggers: sorted(
+                world
+                    .query_filtered::<Entity, (With<ActionTrigger>, With<Spent>)>()
+                    .iter(world)
+                    .collect(),
+            ),
+            broken_walls: sorted(
+                world
+                    .query_filtered::<Entity, (With<BreakableWall>, With<Broken>)>()
+                    .iter(world)
+                    .collect(),
+            ),
+            running_obstacles: sorted(
+                world
+                    .query::<(Entity, &Obstacle)>()
+                    .iter(world)
+                    .filter(|(_, obstacle)| obstacle.running)
+                    .map(|(entity, _)| entity)
+                    .collect(),
+            ),
+            gravity: world.resource::<Gravity>().0,
+        }
+    }
+
+    /// Put the level back the way `changes` says, moving things start again from the beginning so the same shot
+    /// always goes the same way
+    fn restore(&mut self, changes: &LevelChanges) {
+        let world = self.app.world_mut();
+
+        // Neither can fail, all they use is added in `new`
+        let _ = world.run_system_once(restore_walls);
+        let _ = world.run_system_once(reset_triggers);
+
+        for (entity, mut obstacle, mut pos, mut rot) in world
+            .query::<(Entity, &mut Obstacle, &mut Position, &mut Rotation)>()
+            .iter_mut(world)
+        {
+            let (offset, angle) = obstacle.motion.pose(0.0);
+            pos.0 = obstacle.origin + offset;
+            *rot = Rotation::radians(angle);
+            obstacle.elapsed = 0.0;
+            obstacle.running = changes.running_obstacles.contains(&entity);
+        }
+        for mut zone in world.query::<&mut ForceZone>().iter_mut(world) {
+            zone.elapsed = 0.0;
+        }
+        world.resource_mut::<Gravity>().0 = changes.gravity;
+
+        // The same as `open_gates` and `break_walls` do, less the pieces flying off
+        for &entity in changes.open_gates.iter().chain(&changes.broken_walls) {
+            let mut entity = world.entity_mut(entity);
+            if entity.contains::<Gate>() {
+                entity.insert(Open);
+            } else {
+                entity.insert(Broken);
+            }
+            entity.insert(Visibility::Hidden).remove::<Collider>();
+        }
+        for &entity in &changes.spent_triggers {
+            world.entity_mut(entity).insert(Spent);
+        }
+    }
+
+    fn shoot(&mut self, from: &Place, swing: &Swing) -> Shot {
+        self.restore(&from.changes);
+
+        let world = self.app.world_mut();
+
+        world.entity_mut(self.ball).remove::<Sleeping>().insert((
+            Position(from.position),
+            Rotation::IDENTITY,
+            LinearVelocity(swing.velocity()),
+            AngularVelocity(0.0),
+        ));
+
+        let frames = (SHOT_TIMEOUT / FRAME_TIME) as u32;
+        let mut closest = f32::INFINITY;
+
+        for _ in 0..frames {
+            self.app.update();
+
+            let world = self.app.world();
+
+            for (colliding_entities, is_goal) in self.sensors_q.iter(world) {
+                if colliding_entities.contains(&self.ball) {
+                    let outcome = if is_goal {
+                        ShotOutcome::Holed
+                    } else {
+                        ShotOutcome::Lost
+                    };
+                    return Shot { outcome, closest };
+                }
+            }
+
+            let ball = world.entity(self.ball);
+
+            let Some(pos) = ball.get::<Position>() else {
+                break;
+            };
+
+            closest = closest.min(self.distance_to_cup(pos.0));
+
+            let is_outside_width = pos.x < self.bounds.min.x || pos.x > self.bounds.max.x;
+            if is_outside_width || pos.y < self.bounds.min.y {
+                break;
+            }
+
+            if ball.contains::<Sleeping>() {
+                let position = pos.0;
+                let changes = self.changes();
+
+                return Shot {
+                    outcome: ShotOutcome::Stopped(Place { position, changes }),
+                    closest,
+                };
+            }
+        }
+
+        Shot {
+            outcome: ShotOutcome::Lost,
+            closest,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{level_data::RawLevelData, thumbnail::DiscardedAssets};
+
+    /// A flat green with the cup a short putt to the right of the tee
+    const PUTT: &str = r#"{
+        "par": 1,
+        "directions": [
+            [0.0, 0.0], [200.0, 0.0], [0.0, -60.0], [40.0, 0.0], [0.0, 60.0], [200.0, 0.0],
+            [0.0, -300.0], [-440.0, 0.0], [0.0, 300.0]
+        ],
+        "goal_index": 2,
+        "tee": [150.0, 0.0]
+    }"#;
+
+    #[test]
+    fn finds_the_cup() {
+        let raw_level: RawLevelData = serde_json::from_str(PUTT).unwrap();
+        let level = raw_level.into_level(&mut DiscardedAssets).unwrap();
+
+        assert!(fewest_strokes(level).is_some());
+    }
+}
@@ -1,9 +1,13 @@
 use avian2d::prelude::*;
-use bevy::{audio::Volume, prelude::*};
+use bevy::{audio::Volume, prelude::*, utils::HashMap};
 
 use crate::{
-    ball::{Ball, BallHitEvent},
+    ball::{ball_contact_force, Ball, BallHitEvent},
+    gadget::{Gadget, GadgetTriggeredEvent},
     level::LevelState,
+    star::StarCollectedEvent,
+    surface::Surface,
+    water::BallInWaterEvent,
 };
 
 pub struct SoundPlugin;
@@ -11,7 +15,15 @@ pub struct SoundPlugin;
 impl Plugin for SoundPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup);
-        app.add_systems(PostUpdate, react_to_ball_hit);
+        app.add_systems(
+            PostUpdate,
+            (
+                react_to_ball_hit,
+                react_to_ball_in_water,
+                react_to_gadget_triggered,
+                react_to_star_collected,
+            ),
+        );
         app.add_systems(OnEnter(LevelState::Won), spawn_firework_sounds);
         app.add_systems(PostUpdate, play_firework_sounds);
         app.add_systems(PostProcessCollisions, play_ball_bounce_sound);
@@ -22,7 +34,20 @@ impl Plugin for SoundPlugin {
 struct BallHitSound(pub Handle<AudioSource>);
 
 #[derive(Resource)]
-struct BallBounceSound(pub Handle<AudioSource>);
+struct BallBounceSounds(pub HashMap<Surface, Handle<AudioSource>>);
+
+#[derive(Resource)]
+struct SplashSound(pub Handle<AudioSource>);
+
+#[derive(Resource)]
+struct GadgetSounds {
+    bumper: Handle<AudioSource>,
+    spring: Handle<AudioSource>,
+    boost: Handle<AudioSource>,
+}
+
+#[derive(Resource)]
+struct StarSound(pub Handle<AudioSource>);
 
 #[derive(Component, Clone)]
 struct FireworkDelay(pub Timer);
@@ -34,8 +59,29 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     let ball_hit = asset_server.load::<AudioSource>("sounds/ball_hit.ogg");
     commands.insert_resource(BallHitSound(ball_hit));
 
-    let ball_bounce = asset_server.load::<AudioSource>("sounds/ball_bounce.ogg");
-    commands.insert_resource(BallBounceSound(ball_bounce));
+    commands.insert_resource(BallBounceSounds(
+        Surface::ALL
+            .into_iter()
+            .map(|surface| {
+                (
+                    surface,
+                    asset_server.load::<AudioSource>(surface.bounce_sound_path()),
+                )
+            })
+            .collect(),
+    ));
+
+    let splash = asset_server.load::<AudioSource>("sounds/splash.ogg");
+    commands.insert_resource(SplashSound(splash));
+
+    commands.insert_resource(GadgetSounds {
+        bumper: asset_server.load::<AudioSource>("sounds/bumper.ogg"),
+        spring: asset_server.load::<AudioSource>("sounds/spring.ogg"),
+        boost: asset_server.load::<AudioSource>("sounds/boost.ogg"),
+    });
+
+    let star = asset_server.load::<AudioSource>("sounds/star.ogg");
+    commands.insert_resource(StarSound(star));
 
     let firework_launch = asset_server.load::<AudioSource>("sounds/firework_launch.ogg");
     let firework_large_blast_far =
@@ -80,6 +126,42 @@ fn react_to_ball_hit(
     }
 }
 
+fn react_to_ball_in_water(
+    mut commands: Commands,
+    mut event_reader: EventReader<BallInWaterEvent>,
+    sound: Res<SplashSound>,
+) {
+    for _ in event_reader.read() {
+        commands.spawn((AudioPlayer(sound.0.clone()), PlaybackSettings::DESPAWN));
+    }
+}
+
+fn react_to_gadget_triggered(
+    mut commands: Commands,
+    mut event_reader: EventReader<GadgetTriggeredEvent>,
+    sounds: Res<GadgetSounds>,
+) {
+    for GadgetTriggeredEvent(gadget) in event_reader.read() {
+        let sound = match gadget {
+            Gadget::Bumper { .. } => &sounds.bumper,
+            Gadget::Spring { .. } => &sounds.spring,
+            Gadget::Boost { .. } => &sounds.boost,
+        };
+
+        commands.spawn((AudioPlayer(sound.clone()), PlaybackSettings::DESPAWN));
+    }
+}
+
+fn react_to_star_collected(
+    mut commands: Commands,
+    mut event_reader: EventReader<StarCollectedEvent>,
+    sound: Res<StarSound>,
+) {
+    for _ in event_reader.read() {
+        commands.spawn((AudioPlayer(sound.0.clone()), PlaybackSettings::DESPAWN));
+    }
+}
+
 fn spawn_firework_sounds(mut commands: Commands, sounds: Res<FireworkSounds>) {
     for (sound, delay) in &sounds.0 {
         commands.spawn((
@@ -116,9 +198,10 @@ fn play_ball_bounce_sound(
     mut commands: Commands,
     ball_q: Query<(Entity, &ComputedMass), With<Ball>>,
     ball_bounce_sounds_q: Query<(), With<BallBounceSoundMarker>>,
+    surfaces_q: Query<&Surface>,
     collisions: Res<Collisions>,
     gravity: Res<Gravity>,
-    sound: Res<BallBounceSound>,
+    sounds: Res<BallBounceSounds>,
     time: Res<Time<Substeps>>,
 ) {
     let Ok((ball_entity, ball_mass)) = ball_q.get_single() else {
@@ -135,18 +218,22 @@ fn play_ball_bounce_sound(
             continue;
         }
 
-        let weight = ball_mass.value() * gravity.0.y;
-
-        let normal_force = collision.total_normal_impulse / time.delta_secs();
-
-        let net_force = normal_force + weight;
+        let net_force = ball_contact_force(collision, ball_mass, &gravity, &time);
 
         let volume = (net_force / MAX_FORCE_BOUNCE).sqrt().clamp(0.0, 1.0);
 
         if volume > 0.05 {
+            let other_entity = if collision.entity1 == ball_entity {
+                collision.entity2
+            } else {
+                collision.entity1
+            };
+
+            let surface = surfaces_q.get(other_entity).copied().unwrap_or_default();
+
             commands.spawn((
                 BallBounceSoundMarker,
-                AudioPlayer(sound.0.clone()),
+                AudioPlayer(sounds.0[&surface].clone()),
                 PlaybackSettings {
                     mode: bevy::audio::PlaybackMode::Despawn,
                     volume: Volume::new(volume),
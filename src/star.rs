@@ -0,0 +1,164 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::AMBER_300,
+    prelude::*,
+    render::{mesh::Indices, render_asset::RenderAssetUsages},
+};
+use serde::Deserialize;
+
+use crate::{
+    ball::{Ball, BallResetEvent},
+    checkpoint::no_checkpoint_reached,
+    level::{LevelElement, LevelState},
+};
+
+pub struct StarPlugin;
+
+impl Plugin for StarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StarCollectedEvent>()
+            .init_resource::<HoleStars>();
+
+        app.add_systems(
+            Update,
+            (
+                collect_stars.run_if(in_state(LevelState::InPlay)),
+                // Stars picked up on the way to the cup are kept, even if the ball leaves the level afterwards
+                respawn_stars.run_if(
+                    on_event::<BallResetEvent>
+                        .and(not(in_state(LevelState::Won)))
+                        .and(no_checkpoint_reached),
+                ),
+            ),
+        );
+    }
+}
+
+const STAR_RADIUS: f32 = 12.0;
+/// How far the inner corners of a star are from its centre, compared to its points
+const STAR_INNER_RATIO: f32 = 0.45;
+
+pub const STAR_COLOUR: Srgba = AMBER_300;
+
+/// A star as it's written in a level file
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RawStar {
+    /// Centre of the star, from the same origin as the level's outline
+    pub position: Vec2,
+}
+
+pub struct LevelStar {
+    pub position: Vec2,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+}
+
+/// A five pointed star, pointing up
+pub fn star_mesh() -> Mesh {
+    let mut positions = vec![[0.0, 0.0, 0.0]];
+
+    for i in 0..10 {
+        let radius = if i % 2 == 0 {
+            STAR_RADIUS
+        } else {
+            STAR_RADIUS * STAR_INNER_RATIO
+        };
+        let corner = Vec2::from_angle(FRAC_PI_2 + i as f32 * PI / 5.0) * radius;
+        positions.push([corner.x, corner.y, 0.0]);
+    }
+
+    // A fan around the centre
+    let indices = (1..=10u32)
+        .flat_map(|i| [0, i, i % 10 + 1])
+        .collect::<Vec<_>>();
+
+    let mut mesh = Mesh::new(
+        bevy::render::mesh::PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_indices(Indices::U32(indices));
+
+    mesh
+}
+
+#[derive(Component)]
+pub struct Star;
+
+/// A star the ball has picked up since it was last at the tee
+#[derive(Component)]
+pub struct Collected;
+
+#[derive(Event)]
+pub struct StarCollectedEvent;
+
+/// Stars picked up on the current hole since the ball was last at the tee
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct HoleStars(u32);
+
+/// Spawned as a child of the floor, the ball passes through it and picks it up
+pub fn star_bundle(star: &LevelStar, index: usize) -> impl Bundle {
+    (
+        Name::new(format!("Star {index}")),
+        LevelElement,
+        Star,
+        Mesh2d(star.mesh.clone()),
+        MeshMaterial2d(star.material.clone()),
+        Collider::circle(STAR_RADIUS),
+        Sensor,
+        // In front of the terrain and its overlay
+        Transform::from_translation(star.position.extend(0.02)),
+    )
+}
+
+fn collect_stars(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionStarted>,
+    ball_q: Query<Entity, With<Ball>>,
+    star_q: Query<(), (With<Star>, Without<Collected>)>,
+    mut events: EventWriter<StarCollectedEvent>,
+    mut hole_stars: ResMut<HoleStars>,
+) {
+    let Ok(ball_entity) = ball_q.get_single() else {
+        return;
+    };
+
+    for CollisionStarted(entity1, entity2) in collision_events.read() {
+        let other_entity = if *entity1 == ball_entity {
+            *entity2
+        } else if *entity2 == ball_entity {
+            *entity1
+        } else {
+            continue;
+        };
+
+        if star_q.get(other_entity).is_err() {
+            continue;
+        }
+
+        commands
+            .entity(other_entity)
+            .insert((Collected, Visibility::Hidden));
+
+        **hole_stars += 1;
+        events.send(StarCollectedEvent);
+    }
+}
+
+/// Put every collected star back whenever the ball goes back to the tee, including when a new level is loaded
+fn respawn_stars(
+    mut commands: Commands,
+    star_q: Query<Entity, (With<Star>, With<Collected>)>,
+    mut hole_stars: ResMut<HoleStars>,
+) {
+    **hole_stars = 0;
+
+    for entity in &star_q {
+        commands
+            .entity(entity)
+            .remove::<Collected>()
+            .insert(Visibility::Inherited);
+    }
+}
@@ -0,0 +1,64 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{AMBER_300, GREEN_800, SKY_200},
+    prelude::*,
+};
+use serde::Deserialize;
+
+/// What a piece of terrain is made of, this decides how the ball bounces and rolls on it
+#[derive(Component, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Surface {
+    #[default]
+    Fairway,
+    Rough,
+    Sand,
+    Ice,
+}
+
+impl Surface {
+    pub const ALL: [Surface; 4] = [
+        Surface::Fairway,
+        Surface::Rough,
+        Surface::Sand,
+        Surface::Ice,
+    ];
+
+    pub fn friction(&self) -> Friction {
+        match self {
+            Surface::Fairway => Friction::new(0.4),
+            Surface::Rough => Friction::new(0.8),
+            Surface::Sand => Friction::new(1.0).with_combine_rule(CoefficientCombine::Max),
+            Surface::Ice => Friction::new(0.0).with_combine_rule(CoefficientCombine::Min),
+        }
+    }
+
+    pub fn restitution(&self) -> Restitution {
+        match self {
+            Surface::Fairway => Restitution::new(0.4),
+            Surface::Rough => Restitution::new(0.2),
+            // Min beats the ball's Average rule so sand always kills the bounce
+            Surface::Sand => Restitution::new(0.0).with_combine_rule(CoefficientCombine::Min),
+            Surface::Ice => Restitution::new(0.4),
+        }
+    }
+
+    /// Colour of the strip drawn along the top of the terrain, the fairway is left as plain terrain
+    pub fn colour(&self) -> Option<Color> {
+        match self {
+            Surface::Fairway => None,
+            Surface::Rough => Some(GREEN_800.into()),
+            Surface::Sand => Some(AMBER_300.into()),
+            Surface::Ice => Some(SKY_200.into()),
+        }
+    }
+
+    pub fn bounce_sound_path(&self) -> &'static str {
+        match self {
+            Surface::Fairway => "sounds/ball_bounce.ogg",
+            Surface::Rough => "sounds/ball_bounce_rough.ogg",
+            Surface::Sand => "sounds/ball_bounce_sand.ogg",
+            Surface::Ice => "sounds/ball_bounce_ice.ogg",
+        }
+    }
+}
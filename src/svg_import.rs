@@ -0,0 +1,784 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    math::bounding::{Aabb2d, BoundingVolume},
+    prelude::*,
+};
+use roxmltree::{Document, Node};
+
+use crate::{
+    cup::RawCup,
+    direction::{directions_to_points, Curve, Direction, DEFAULT_CURVE_RESOLUTION},
+    level_data::{level_name_from_path, signed_area, Level, RawLevelData},
+    level_validation::LevelError,
+    physics::RawLevelPhysics,
+    terrain::RawTerrain,
+};
+
+pub struct SvgImportPlugin;
+
+impl Plugin for SvgImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset_loader::<SvgLevelLoader>();
+    }
+}
+
+/// Imported outlines are scaled to be this wide, which is what the camera is set up for
+const WORLD_WIDTH: f32 = 720.0;
+
+const OUTLINE_ID: &str = "outline";
+const TEE_ID: &str = "tee";
+const CUP_ID: &str = "cup";
+
+const DEFAULT_PAR: u32 = 3;
+
+/// How far apart the end of the path and its start can be before a line is added to close it
+const CLOSE_TOLERANCE: f32 = 0.01;
+
+#[derive(Debug)]
+pub enum SvgImportError {
+    Io(std::io::Error),
+    Utf8(std::str::Utf8Error),
+    Xml(roxmltree::Error),
+    NoOutline,
+    BadPathData { position: usize },
+    UnsupportedCommand { command: char },
+    Invalid(LevelError),
+}
+
+impl std::fmt::Display for SvgImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgImportError::Io(err) => write!(f, "Could not read SVG file: {err}"),
+            SvgImportError::Utf8(err) => write!(f, "SVG file isn't valid UTF-8: {err}"),
+            SvgImportError::Xml(err) => write!(f, "Could not parse SVG file: {err}"),
+            SvgImportError::NoOutline => {
+                write!(f, "SVG file has no path with the id \"{OUTLINE_ID}\"")
+            }
+            SvgImportError::BadPathData { position } => {
+                write!(f, "Outline path data is malformed at character {position}")
+            }
+            SvgImportError::UnsupportedCommand { command } => {
+                write!(f, "Outline path uses '{command}', which isn't supported")
+            }
+            SvgImportError::Invalid(err) => write!(f, "Invalid level: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SvgImportError {}
+
+impl From<std::io::Error> for SvgImportError {
+    fn from(err: std::io::Error) -> Self {
+        SvgImportError::Io(err)
+    }
+}
+
+impl From<std::str::Utf8Error> for SvgImportError {
+    fn from(err: std::str::Utf8Error) -> Self {
+        SvgImportError::Utf8(err)
+    }
+}
+
+impl From<roxmltree::Error> for SvgImportError {
+    fn from(err: roxmltree::Error) -> Self {
+        SvgImportError::Xml(err)
+    }
+}
+
+impl From<LevelError> for SvgImportError {
+    fn from(err: LevelError) -> Self {
+        SvgImportError::Invalid(err)
+    }
+}
+
+#[derive(Default)]
+struct SvgLevelLoader;
+
+impl AssetLoader for SvgLevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = SvgImportError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Level, SvgImportError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut raw_level = import_svg(std::str::from_utf8(&bytes)?)?;
+        raw_level
+            .name
+            .get_or_insert_with(|| level_name_from_path(load_context.path()));
+
+        Ok(raw_level.into_level(load_context)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.svg"]
+    }
+}
+
+/// Read a level from an SVG drawing. The terrain is the path with the id `outline`, drawn with the playing
+/// surface going left to right. Elements with the ids `tee` and `cup` mark where the ball starts (the bottom
+/// middle of the element) and the notch the cup sits in (its bottom left). Without them the ball starts where
+/// the path does and the cup goes in the first notch. `data-name`, `data-par`, `data-lives` and `data-author`
+/// on the root element fill in the rest. Transforms aren't applied, so flatten them before exporting.
+pub fn import_svg(svg: &str) -> Result<RawLevelData, SvgImportError> {
+    let document = Document::parse(svg)?;
+    let root = document.root_element();
+
+    let find = |id: &str| {
+        document
+            .descendants()
+            .find(|node| node.attribute("id") == Some(id))
+    };
+
+    let path_data = find(OUTLINE_ID)
+        .filter(|node| node.has_tag_name("path"))
+        .and_then(|node| node.attribute("d"))
+        .ok_or(SvgImportError::NoOutline)?;
+
+    let mut segments = parse_path(path_data)?;
+
+    // SVG's y axis points down
+    let flip = |point: Vec2| Vec2::new(point.x, -point.y);
+    for segment in &mut segments {
+        segment.map_points(flip);
+    }
+
+    let Some(start) = segments.first().map(|segment| segment.from) else {
+        return Err(SvgImportError::NoOutline);
+    };
+
+    // Levels go clockwise, with the terrain below the surface as it goes right
+    let ends: Vec<Vec2> = std::iter::once(start)
+        .chain(segments.iter().map(|segment| segment.to))
+        .collect();
+    if signed_area(&ends) > 0.0 {
+        segments = segments.into_iter().rev().map(Segment::reversed).collect();
+    }
+
+    let unscaled: Vec<Direction> = std::iter::once(Direction::Line(start))
+        .chain(segments.iter().map(Segment::direction))
+        .collect();
+    let (unscaled_points, _) = directions_to_points(&unscaled, DEFAULT_CURVE_RESOLUTION);
+    let width = Aabb2d::from_point_cloud(Isometry2d::IDENTITY, &unscaled_points)
+        .half_size()
+        .x
+        * 2.0;
+    let scale = if width > 0.0 {
+        WORLD_WIDTH / width
+    } else {
+        1.0
+    };
+
+    for segment in &mut segments {
+        segment.map_points(|point| point * scale);
+    }
+    let start = start * scale;
+
+    let directions: Vec<Direction> = std::iter::once(Direction::Line(start))
+        .chain(segments.iter().map(Segment::direction))
+        .collect();
+    let (points, direction_ends) = directions_to_points(&directions, DEFAULT_CURVE_RESOLUTION);
+
+    let to_world = |point: Vec2| flip(point) * scale;
+
+    let tee = find(TEE_ID)
+        .and_then(|node| marker_bounds(&node))
+        .map(|(min, max)| to_world(Vec2::new((min.x + max.x) / 2.0, max.y)) - start)
+        .unwrap_or_default();
+
+    let goal_index = match find(CUP_ID).and_then(|node| marker_bounds(&node)) {
+        Some((min, max)) => {
+            let bottom_left = to_world(Vec2::new(min.x, max.y));
+
+            (1..directions.len())
+                .min_by(|&a, &b| {
+                    let distance =
+                        |index: usize| points[direction_ends[index]].distance(bottom_left);
+                    distance(a).total_cmp(&distance(b))
+                })
+                .unwrap_or_default()
+        }
+        None => first_notch(&directions).unwrap_or_default(),
+    };
+
+    let number = |name: &str| root.attribute(name).and_then(|value| value.parse().ok());
+
+    Ok(RawLevelData {
+        name: root.attribute("data-name").map(str::to_string),
+        par: number("data-par").unwrap_or(DEFAULT_PAR),
+        lives: number("data-lives"),
+        author: root.attribute("data-author").map(str::to_string),
+        directions,
+        goal_index,
+        cup: RawCup::default(),
+        alternate_cups: Vec::new(),
+        tee,
+        alternate_tees: Vec::new(),
+        holes: Vec::new(),
+        islands: Vec::new(),
+        water: Vec::new(),
+        obstacles: Vec::new(),
+        gadgets: Vec::new(),
+        zones: Vec::new(),
+        portals: Vec::new(),
+        breakables: Vec::new(),
+        stars: Vec::new(),
+        checkpoints: Vec::new(),
+        gates: Vec::new(),
+        triggers: Vec::new(),
+        curve_resolution: DEFAULT_CURVE_RESOLUTION,
+        surfaces: Vec::new(),
+        terrain: RawTerrain::default(),
+        physics: RawLevelPhysics::default(),
+    })
+}
+
+/// Index of the first direction that goes down the left wall of a notch, followed by its floor and right wall
+fn first_notch(directions: &[Direction]) -> Option<usize> {
+    let is_down = |offset: Vec2| offset.y < 0.0 && offset.x.abs() < -offset.y;
+    let is_right = |offset: Vec2| offset.x > 0.0 && offset.y.abs() < offset.x;
+    let is_up = |offset: Vec2| offset.y > 0.0 && offset.x.abs() < offset.y;
+
+    directions.windows(3).position(|window| {
+        is_down(window[0].offset()) && is_right(window[1].offset()) && is_up(window[2].offset())
+    })
+}
+
+/// Top left and bottom right corners of a rect, circle or ellipse, in SVG coordinates
+fn marker_bounds(node: &Node) -> Option<(Vec2, Vec2)> {
+    let number = |name: &str| -> Option<f32> { node.attribute(name)?.parse().ok() };
+
+    match node.tag_name().name() {
+        "rect" => {
+            let min = Vec2::new(number("x").unwrap_or(0.0), number("y").unwrap_or(0.0));
+            Some((min, min + Vec2::new(number("width")?, number("height")?)))
+        }
+        "circle" => {
+            let centre = Vec2::new(number("cx").unwrap_or(0.0), number("cy").unwrap_or(0.0));
+            let radius = Vec2::splat(number("r")?);
+            Some((centre - radius, centre + radius))
+        }
+        "ellipse" => {
+            let centre = Vec2::new(number("cx").unwrap_or(0.0), number("cy").unwrap_or(0.0));
+            let radius = Vec2::new(number("rx")?, number("ry")?);
+            Some((centre - radius, centre + radius))
+        }
+        _ => None,
+    }
+}
+
+/// One piece of a path in absolute coordinates
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    from: Vec2,
+    kind: SegmentKind,
+    to: Vec2,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SegmentKind {
+    Line,
+    Quadratic { control: Vec2 },
+    Cubic { control_1: Vec2, control_2: Vec2 },
+}
+
+impl Segment {
+    fn map_points(&mut self, f: impl Fn(Vec2) -> Vec2) {
+        self.from = f(self.from);
+        self.to = f(self.to);
+
+        match &mut self.kind {
+            SegmentKind::Line => {}
+            SegmentKind::Quadratic { control } => *control = f(*control),
+            SegmentKind::Cubic {
+                control_1,
+                control_2,
+            } => {
+                *control_1 = f(*control_1);
+                *control_2 = f(*control_2);
+            }
+        }
+    }
+
+    fn reversed(self) -> Segment {
+        let kind = match self.kind {
+            SegmentKind::Cubic {
+                control_1,
+                control_2,
+            } => SegmentKind::Cubic {
+                control_1: control_2,
+                control_2: control_1,
+            },
+            kind => kind,
+        };
+
+        Segment {
+            from: self.to,
+            kind,
+            to: self.from,
+        }
+    }
+
+    fn direction(&self) -> Direction {
+        let to = self.to - self.from;
+
+        match self.kind {
+            SegmentKind::Line => Direction::Line(to),
+            SegmentKind::Quadratic { control } => Direction::Curve(Curve::Quadratic {
+                control: control - self.from,
+                to,
+            }),
+            SegmentKind::Cubic {
+                control_1,
+                control_2,
+            } => Direction::Curve(Curve::Cubic {
+                control_1: control_1 - self.from,
+                control_2: control_2 - self.from,
+                to,
+            }),
+        }
+    }
+}
+
+enum Token {
+    Command(char),
+    Number(f32),
+}
+
+fn tokenize(data: &str) -> Result<Vec<(usize, Token)>, SvgImportError> {
+    let bytes = data.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i] as char;
+
+        if c.is_ascii_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            tokens.push((i, Token::Command(c)));
+            i += 1;
+        } else {
+            // A number ends at the next sign that isn't part of an exponent, or at a second decimal point
+            let start = i;
+            let mut seen_point = false;
+            let mut seen_exponent = false;
+
+            if matches!(c, '+' | '-') {
+                i += 1;
+            }
+
+            while i < bytes.len() {
+                match bytes[i] as char {
+                    '0'..='9' => {}
+                    '.' if !seen_point && !seen_exponent => seen_point = true,
+                    'e' | 'E' if !seen_exponent => {
+                        seen_exponent = true;
+                        if matches!(bytes.get(i + 1), Some(b'+' | b'-')) {
+                            i += 1;
+                        }
+                    }
+                    _ => break,
+                }
+                i += 1;
+            }
+
+            let number = data[start..i]
+                .parse()
+                .map_err(|_| SvgImportError::BadPathData { position: start })?;
+            tokens.push((start, Token::Number(number)));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Absolute segments of the first subpath in `data`, closed back to where it started
+fn parse_path(data: &str) -> Result<Vec<Segment>, SvgImportError> {
+    let tokens = tokenize(data)?;
+    let mut tokens = tokens.iter().peekable();
+
+    let mut segments = Vec::new();
+    let mut start = Vec2::ZERO;
+    let mut current = Vec2::ZERO;
+    // For the smooth curve commands, which reflect the previous control point
+    let mut last_control: Option<Vec2> = None;
+    let mut command = None;
+
+    loop {
+        let next_command = match tokens.peek() {
+            None => break,
+            Some((_, Token::Command(c))) => {
+                tokens.next();
+                *c
+            }
+            Some((position, Token::Number(_))) => match command {
+                // Numbers after a move are lines to each point
+                Some('M') => 'L',
+                Some('m') => 'l',
+                Some(c) if !matches!(c, 'Z' | 'z') => c,
+                _ => {
+                    return Err(SvgImportError::BadPathData {
+                        position: *position,
+                    })
+                }
+            },
+        };
+
+        let mut number = || -> Result<f32, SvgImportError> {
+            match tokens.next() {
+                Some((_, Token::Number(value))) => Ok(*value),
+                Some((position, Token::Command(_))) => Err(SvgImportError::BadPathData {
+                    position: *position,
+                }),
+                None => Err(SvgImportError::BadPathData {
+                    position: data.len(),
+                }),
+            }
+        };
+
+        let relative = next_command.is_ascii_lowercase();
+        let origin = if relative { current } else { Vec2::ZERO };
+        let mut point = |origin: Vec2| -> Result<Vec2, SvgImportError> {
+            Ok(origin + Vec2::new(number()?, number()?))
+        };
+
+        let (kind, to) = match next_command.to_ascii_uppercase() {
+            'M' => {
+                // Only the first subpath is the outline
+                if !segments.is_empty() {
+                    break;
+                }
+
+                current = point(origin)?;
+                start = current;
+                command = Some(next_command);
+                last_control = None;
+                continue;
+            }
+            'Z' => {
+                command = Some(next_command);
+                break;
+            }
+            'L' => (SegmentKind::Line, point(origin)?),
+            'H' => {
+                let x = number()? + origin.x;
+                (SegmentKind::Line, Vec2::new(x, current.y))
+            }
+            'V' => {
+                let y = number()? + origin.y;
+                (SegmentKind::Line, Vec2::new(current.x, y))
+            }
+            'Q' => {
+                let control = point(origin)?;
+                (SegmentKind::Quadratic { control }, point(origin)?)
+            }
+            'T' => {
+                let control = 2.0 * current - last_control.unwrap_or(current);
+                (SegmentKind::Quadratic { control }, point(origin)?)
+            }
+            'C' => {
+                let control_1 = point(origin)?;
+                let control_2 = point(origin)?;
+                (
+                    SegmentKind::Cubic {
+                        control_1,
+                        control_2,
+                    },
+                    point(origin)?,
+                )
+            }
+            'S' => {
+                let control_1 = 2.0 * current - last_control.unwrap_or(current);
+                let control_2 = point(origin)?;
+                (
+                    SegmentKind::Cubic {
+                        control_1,
+                        control_2,
+                    },
+                    point(origin)?,
+                )
+            }
+            _ => {
+                return Err(SvgImportError::UnsupportedCommand {
+                    command: next_command,
+                })
+            }
+        };
+
+        last_control = match kind {
+            SegmentKind::Quadratic { control } => Some(control),
+            SegmentKind::Cubic { control_2, .. } => Some(control_2),
+            SegmentKind::Line => None,
+        };
+
+        segments.push(Segment {
+            from: current,
+            kind,
+            to,
+        });
+        current = to;
+        command = Some(next_command);
+    }
+
+    if current.distance(start) > CLOSE_TOLERANCE {
+        segments.push(Segment {
+            from: current,
+            kind: SegmentKind::Line,
+            to: start,
+        });
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every point that defines the segments, in order
+    fn points(segments: &[Segment]) -> Vec<Vec2> {
+        segments
+            .iter()
+            .flat_map(|segment| {
+                let controls = match segment.kind {
+                    SegmentKind::Line => vec![],
+                    SegmentKind::Quadratic { control } => vec![control],
+                    SegmentKind::Cubic {
+                        control_1,
+                        control_2,
+                    } => vec![control_1, control_2],
+                };
+                std::iter::once(segment.from)
+                    .chain(controls)
+                    .chain(std::iter::once(segment.to))
+            })
+            .collect()
+    }
+
+    fn ends(segments: &[Segment]) -> Vec<Vec2> {
+        segments.iter().map(|segment| segment.to).collect()
+    }
+
+    fn numbers(data: &str) -> Vec<f32> {
+        tokenize(data)
+            .unwrap()
+            .into_iter()
+            .filter_map(|(_, token)| match token {
+                Token::Number(value) => Some(value),
+                Token::Command(_) => None,
+            })
+            .collect()
+    }
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.abs_diff_eq(expected, 1e-3),
+            "{actual} isn't {expected}"
+        );
+    }
+
+    #[test]
+    fn absolute_commands() {
+        let segments = parse_path(
+            "M 0 0 L 10 0 H 20 V 10 Q 25 15 30 10 T 40 10 C 45 0 50 0 55 10 S 65 20 70 10 Z",
+        )
+        .unwrap();
+
+        assert_eq!(
+            ends(&segments),
+            [
+                Vec2::new(10.0, 0.0),
+                Vec2::new(20.0, 0.0),
+                Vec2::new(20.0, 10.0),
+                Vec2::new(30.0, 10.0),
+                Vec2::new(40.0, 10.0),
+                Vec2::new(55.0, 10.0),
+                Vec2::new(70.0, 10.0),
+                Vec2::ZERO,
+            ]
+        );
+
+        // Smooth curves reflect the previous control point about the current point
+        assert!(matches!(
+            segments[4].kind,
+            SegmentKind::Quadratic { control } if control == Vec2::new(35.0, 5.0)
+        ));
+        assert!(matches!(
+            segments[6].kind,
+            SegmentKind::Cubic { control_1, control_2 }
+                if control_1 == Vec2::new(60.0, 20.0) && control_2 == Vec2::new(65.0, 20.0)
+        ));
+    }
+
+    #[test]
+    fn relative_commands_match_absolute() {
+        let absolute = parse_path(
+            "M 5 5 L 15 5 H 25 V 15 Q 30 20 35 15 T 45 15 C 50 5 55 5 60 15 S 70 25 75 15 Z",
+        )
+        .unwrap();
+        let relative = parse_path(
+            "m 5 5 l 10 0 h 10 v 10 q 5 5 10 0 t 10 0 c 5 -10 10 -10 15 0 s 10 10 15 0 z",
+        )
+        .unwrap();
+
+        assert_eq!(points(&relative), points(&absolute));
+    }
+
+    #[test]
+    fn numbers_after_a_move_are_lines() {
+        let absolute = parse_path("M 0 0 10 0 10 10 Z").unwrap();
+        assert_eq!(
+            ends(&absolute),
+            [Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), Vec2::ZERO]
+        );
+
+        let relative = parse_path("m 5 5 10 0 0 10 z").unwrap();
+        assert_eq!(
+            ends(&relative),
+            [
+                Vec2::new(15.0, 5.0),
+                Vec2::new(15.0, 15.0),
+                Vec2::new(5.0, 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_repeat_for_extra_numbers() {
+        let segments = parse_path("M 0 0 L 10 0 20 10 h 5 5 v -5 -5").unwrap();
+
+        assert_eq!(
+            ends(&segments),
+            [
+                Vec2::new(10.0, 0.0),
+                Vec2::new(20.0, 10.0),
+                Vec2::new(25.0, 10.0),
+                Vec2::new(30.0, 10.0),
+                Vec2::new(30.0, 5.0),
+                Vec2::new(30.0, 0.0),
+                Vec2::ZERO,
+            ]
+        );
+    }
+
+    #[test]
+    fn numbers_without_separators() {
+        assert_eq!(numbers("1-2"), [1.0, -2.0]);
+        assert_eq!(numbers(".5.5"), [0.5, 0.5]);
+        assert_eq!(numbers("-1.5-.5"), [-1.5, -0.5]);
+        assert_eq!(numbers("M0,0L10-5"), [0.0, 0.0, 10.0, -5.0]);
+    }
+
+    #[test]
+    fn numbers_with_exponents() {
+        assert_eq!(numbers("1e2 1E-1 -2.5e+1"), [100.0, 0.1, -25.0]);
+        // The sign after an exponent belongs to it, the next one starts a new number
+        assert_eq!(numbers("1e-1-1"), [0.1, -1.0]);
+    }
+
+    #[test]
+    fn bad_numbers_are_reported() {
+        assert!(matches!(
+            tokenize("M 0 0 L 1e 2"),
+            Err(SvgImportError::BadPathData { position: 8 })
+        ));
+    }
+
+    #[test]
+    fn open_paths_are_closed() {
+        let segments = parse_path("M 0 0 L 10 0 L 10 10").unwrap();
+        let last = segments.last().unwrap();
+
+        assert_eq!(segments.len(), 3);
+        assert!(matches!(last.kind, SegmentKind::Line));
+        assert_eq!((last.from, last.to), (Vec2::new(10.0, 10.0), Vec2::ZERO));
+    }
+
+    #[test]
+    fn paths_ending_at_their_start_arent_closed_again() {
+        let segments = parse_path("M 0 0 L 10 0 L 10 10 L 0 0 Z").unwrap();
+
+        assert_eq!(segments.len(), 3);
+    }
+
+    #[test]
+    fn only_the_first_subpath_is_read() {
+        let segments = parse_path("M 0 0 L 10 0 L 10 10 Z M 50 50 L 60 50").unwrap();
+
+        assert_eq!(
+            ends(&segments),
+            [Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0), Vec2::ZERO]
+        );
+    }
+
+    #[test]
+    fn unsupported_commands_are_reported() {
+        assert!(matches!(
+            parse_path("M 0 0 A 5 5 0 0 1 10 0 Z"),
+            Err(SvgImportError::UnsupportedCommand { command: 'A' })
+        ));
+    }
+
+    /// 100 wide with a 10 by 10 notch, drawn in SVG's coordinates with y going down
+    fn level_svg(markers: &str) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" data-name="Notch" data-par="2">
+                <path id="outline" d="M 0 0 H 40 V 10 H 50 V 0 H 100 V 50 H 0 Z"/>
+                {markers}
+            </svg>"#
+        )
+    }
+
+    #[test]
+    fn outline_is_scaled_to_the_world_width() {
+        let raw_level = import_svg(&level_svg("")).unwrap();
+        let (points, _) = directions_to_points(&raw_level.directions, DEFAULT_CURVE_RESOLUTION);
+        let bounds = Aabb2d::from_point_cloud(Isometry2d::IDENTITY, &points);
+
+        assert!((bounds.half_size().x * 2.0 - WORLD_WIDTH).abs() < 1e-3);
+        assert!((bounds.half_size().y * 2.0 - 360.0).abs() < 1e-3);
+        assert_eq!(raw_level.name.as_deref(), Some("Notch"));
+        assert_eq!(raw_level.par, 2);
+    }
+
+    #[test]
+    fn outline_goes_clockwise() {
+        let raw_level = import_svg(&level_svg("")).unwrap();
+        let (points, _) = directions_to_points(&raw_level.directions, DEFAULT_CURVE_RESOLUTION);
+
+        assert!(signed_area(&points) < 0.0);
+    }
+
+    #[test]
+    fn markers_place_the_tee_and_cup() {
+        let raw_level = import_svg(&level_svg(
+            r#"<rect id="tee" x="5" y="-4" width="4" height="4"/>
+               <circle id="cup" cx="45" cy="8" r="2"/>"#,
+        ))
+        .unwrap();
+
+        // The bottom middle of the tee marker, from the start of the outline
+        assert_near(raw_level.tee, Vec2::new(50.4, 0.0));
+        // The direction ending nearest the cup marker's bottom left is the left wall of the notch
+        assert_eq!(raw_level.goal_index, 2);
+        assert_near(
+            raw_level.directions[raw_level.goal_index].offset(),
+            Vec2::new(0.0, -72.0),
+        );
+    }
+
+    #[test]
+    fn cup_goes_in_the_first_notch_without_a_marker() {
+        let raw_level = import_svg(&level_svg("")).unwrap();
+
+        assert_eq!(raw_level.tee, Vec2::ZERO);
+        assert_eq!(raw_level.goal_index, 2);
+    }
+}
@@ -118,7 +118,8 @@ struct ChainStart;
 #[derive(Component)]
 struct ChainIndex(pub u8);
 
-const CHAIN_LENGTH: u8 = 7;
+/// Also the most power a swing can have
+pub const CHAIN_LENGTH: u8 = 7;
 const CHAIN_SPACING: u8 = 25;
 
 const WINDUP_DISTANCE_IN_PIXELS: f32 = ((CHAIN_LENGTH + 1) * CHAIN_SPACING) as f32;
@@ -193,6 +194,13 @@ pub struct Swing {
     pub angle: f32,
 }
 
+impl Swing {
+    /// How fast the ball goes straight after being hit
+    pub fn velocity(&self) -> Vec2 {
+        Vec2::from_angle(self.angle) * self.power as f32 * LAUNCH_FACTOR
+    }
+}
+
 fn calculate_swing_power(
     mouse_coords: Res<MouseCoords>,
     start_of_swing: Res<StartOfSwing>,
@@ -261,7 +269,7 @@ fn swing(
 
     let mut ball_vel = ball_q.single_mut();
 
-    ball_vel.0 = Vec2::from_angle(swing.angle) * swing.power as f32 * LAUNCH_FACTOR;
+    ball_vel.0 = swing.velocity();
 
     event_writer.send(BallHitEvent {
         speed: ball_vel.length(),
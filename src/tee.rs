@@ -0,0 +1,90 @@
+use bevy::{prelude::*, utils::SystemTime};
+use serde::Deserialize;
+
+pub struct TeePlugin;
+
+impl Plugin for TeePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TeeChoice>();
+    }
+}
+
+/// How far back a tee is, the further back the harder the hole
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TeeBox {
+    Forward,
+    #[default]
+    Regular,
+    Back,
+}
+
+/// An extra tee as it's written in a level file, positioned the same way as the level's `tee`
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RawTee {
+    pub position: Vec2,
+    #[serde(default)]
+    pub tee_box: TeeBox,
+}
+
+/// A tee the ball can start from, in the level's space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelTee {
+    pub position: Vec2,
+    pub tee_box: TeeBox,
+}
+
+/// Which of a level's tees the ball starts from, picked in the menu
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TeeChoice {
+    Box(TeeBox),
+    /// A different one of the level's tees each time it's loaded
+    Random,
+}
+
+impl Default for TeeChoice {
+    fn default() -> Self {
+        TeeChoice::Box(TeeBox::default())
+    }
+}
+
+impl TeeChoice {
+    /// The order the menu goes through them in
+    pub fn next(self) -> TeeChoice {
+        match self {
+            TeeChoice::Box(TeeBox::Forward) => TeeChoice::Box(TeeBox::Regular),
+            TeeChoice::Box(TeeBox::Regular) => TeeChoice::Box(TeeBox::Back),
+            TeeChoice::Box(TeeBox::Back) => TeeChoice::Random,
+            TeeChoice::Random => TeeChoice::Box(TeeBox::Forward),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TeeChoice::Box(TeeBox::Forward) => "FORWARD TEES",
+            TeeChoice::Box(TeeBox::Regular) => "REGULAR TEES",
+            TeeChoice::Box(TeeBox::Back) => "BACK TEES",
+            TeeChoice::Random => "RANDOM TEES",
+        }
+    }
+
+    /// Where the ball starts, levels without the chosen tee box fall back to their first tee
+    pub fn pick(self, tees: &[LevelTee]) -> Vec2 {
+        let tee = match self {
+            TeeChoice::Box(tee_box) => tees.iter().find(|tee| tee.tee_box == tee_box),
+            TeeChoice::Random if !tees.is_empty() => {
+                // Doesn't need to be any more random than the time it's picked at
+                let nanos = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map(|since_epoch| since_epoch.subsec_nanos() as usize)
+                    .unwrap_or_default();
+                tees.get(nanos % tees.len())
+            }
+            TeeChoice::Random => None,
+        };
+
+        tee.or(tees.first())
+            .map(|tee| tee.position)
+            .unwrap_or_default()
+    }
+}
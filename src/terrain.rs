@@ -0,0 +1,97 @@
+use bevy::{
+    color::palettes::tailwind::{GREEN_600, INDIGO_950, PURPLE_900, STONE_500},
+    image::{ImageAddressMode, ImageSampler, ImageSamplerDescriptor},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::level_validation::LevelError;
+
+/// How many world units one copy of a terrain texture covers before it repeats
+pub const TERRAIN_TEXTURE_SIZE: f32 = 128.0;
+
+/// Nearest filtering like the rest of the game, but repeating so textures tile across the terrain
+pub fn tiling_sampler() -> ImageSampler {
+    ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        ..ImageSamplerDescriptor::nearest()
+    })
+}
+
+/// How a level's terrain is drawn, written as the name of a theme or as `{ "texture": ..., "colour": ... }`
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum RawTerrain {
+    Theme(TerrainTheme),
+    Custom {
+        /// Path from the assets folder
+        texture: Option<String>,
+        /// Hex code like `#4d7c0f`
+        colour: Option<String>,
+    },
+}
+
+impl Default for RawTerrain {
+    fn default() -> Self {
+        RawTerrain::Theme(TerrainTheme::default())
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TerrainTheme {
+    #[default]
+    Plain,
+    Grass,
+    Stone,
+    Night,
+}
+
+impl TerrainTheme {
+    /// Terrain textures are greyscale so the colour decides what they look like
+    pub fn colour(&self) -> Srgba {
+        match self {
+            TerrainTheme::Plain => PURPLE_900,
+            TerrainTheme::Grass => GREEN_600,
+            TerrainTheme::Stone => STONE_500,
+            TerrainTheme::Night => INDIGO_950,
+        }
+    }
+
+    pub fn texture_path(&self) -> Option<&'static str> {
+        match self {
+            TerrainTheme::Plain => None,
+            TerrainTheme::Grass | TerrainTheme::Night => Some("images/terrain/grass.png"),
+            TerrainTheme::Stone => Some("images/terrain/stone.png"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Terrain {
+    pub colour: Srgba,
+    pub texture: Option<String>,
+}
+
+impl RawTerrain {
+    pub fn resolve(self) -> Result<Terrain, LevelError> {
+        match self {
+            RawTerrain::Theme(theme) => Ok(Terrain {
+                colour: theme.colour(),
+                texture: theme.texture_path().map(str::to_string),
+            }),
+            RawTerrain::Custom { texture, colour } => {
+                let colour = match colour {
+                    Some(hex) => {
+                        Srgba::hex(&hex).map_err(|_| LevelError::InvalidColour { colour: hex })?
+                    }
+                    None if texture.is_some() => Srgba::WHITE,
+                    None => TerrainTheme::default().colour(),
+                };
+
+                Ok(Terrain { colour, texture })
+            }
+        }
+    }
+}
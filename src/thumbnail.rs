@@ -0,0 +1,260 @@
+use std::{error::Error, fmt::Write, fs, path::Path};
+
+use bevy::{
+    color::{palettes::css::WHITE, ColorToPacked},
+    math::bounding::{Aabb2d, BoundingVolume},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::{
+    level::BALL_RADIUS,
+    level_data::{level_name_from_path, Level, LevelAssets, RawLevelData, WATER_COLOUR},
+    level_validation::{is_inside_terrain, is_point_inside},
+    svg_import::import_svg,
+};
+
+/// Space left around the terrain, in world units
+const PADDING: f32 = 20.0;
+const THUMBNAIL_WIDTH: u32 = 256;
+const MARKER_COLOUR: Srgba = WHITE;
+
+/// Build a level straight from its file, without an asset server. The meshes are built and thrown away
+/// so this goes through exactly the same steps as loading a level in the game.
+pub fn read_level(path: &Path) -> Result<Level, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    let file_name = path.to_string_lossy();
+
+    let mut raw_level: RawLevelData = if file_name.ends_with(".level.svg") {
+        import_svg(&contents)?
+    } else {
+        serde_json::from_str(&contents)?
+    };
+    raw_level
+        .name
+        .get_or_insert_with(|| level_name_from_path(path));
+
+    Ok(raw_level.into_level(&mut DiscardedAssets)?)
+}
+
+/// Thumbnails only need a level's shapes, so its meshes and materials aren't kept
+pub(crate) struct DiscardedAssets;
+
+impl LevelAssets for DiscardedAssets {
+    fn add_mesh(&mut self, _label: String, _mesh: Mesh) -> Handle<Mesh> {
+        Handle::default()
+    }
+
+    fn add_material(&mut self, _label: String, _material: ColorMaterial) -> Handle<ColorMaterial> {
+        Handle::default()
+    }
+
+    fn load_texture(&mut self, _path: String) -> Handle<Image> {
+        Handle::default()
+    }
+
+    fn load_sound(&mut self, _path: String) -> Handle<AudioSource> {
+        Handle::default()
+    }
+}
+
+/// Write an SVG and a PNG thumbnail of each level into `output`, named after the level's file
+pub fn export_thumbnails(output: &Path, level_paths: &[String]) {
+    if let Err(err) = fs::create_dir_all(output) {
+        eprintln!("Could not create {}: {err}", output.display());
+        return;
+    }
+
+    for level_path in level_paths {
+        let level_path = Path::new(level_path);
+        let name = level_name_from_path(level_path);
+
+        let result = read_level(level_path).and_then(|level| {
+            fs::write(output.join(format!("{name}.svg")), level_to_svg(&level))?;
+            level_to_image(&level, THUMBNAIL_WIDTH)
+                .try_into_dynamic()?
+                .save(output.join(format!("{name}.png")))?;
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => println!("Wrote thumbnails for {}", level_path.display()),
+            Err(err) => eprintln!("Skipping {}: {err}", level_path.display()),
+        }
+    }
+}
+
+/// Area the thumbnail covers, in world units
+fn thumbnail_bounds(level: &Level) -> Aabb2d {
+    let mut bounds = Aabb2d::from_point_cloud(Isometry2d::IDENTITY, &level.points);
+    for tee in &level.tees {
+        bounds.min = bounds.min.min(tee.position - BALL_RADIUS);
+        bounds.max = bounds.max.max(tee.position + BALL_RADIUS);
+    }
+    bounds.grow(Vec2::splat(PADDING))
+}
+
+/// Which part of the level is at `point`, from the top down
+fn colour_at(level: &Level, point: Vec2) -> Option<Srgba> {
+    let on_tee = level
+        .tees
+        .iter()
+        .any(|tee| point.distance(tee.position) <= BALL_RADIUS);
+    let in_cup = level.cups.iter().any(|level_cup| {
+        let cup_min = level_cup.bottom_left;
+        let cup_max = cup_min + Vec2::new(level_cup.cup.width, level_cup.cup.depth);
+        point.cmpge(cup_min).all() && point.cmple(cup_max).all()
+    });
+
+    if on_tee || in_cup {
+        Some(MARKER_COLOUR)
+    } else if level.water.iter().any(|pool| is_point_inside(pool, point)) {
+        Some(WATER_COLOUR)
+    } else if is_inside_terrain(&level.points, &level.holes, &level.islands, point) {
+        Some(level.terrain.colour)
+    } else {
+        None
+    }
+}
+
+/// Rasterise the terrain, water, tees and cups into an image `width` pixels wide, with a transparent background
+pub fn level_to_image(level: &Level, width: u32) -> Image {
+    let bounds = thumbnail_bounds(level);
+    let size = bounds.max - bounds.min;
+    let pixels_per_unit = width as f32 / size.x;
+    let height = ((size.y * pixels_per_unit).ceil() as u32).max(1);
+
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+
+    for y in 0..height {
+        for x in 0..width {
+            // Sample the middle of each pixel, images go top to bottom
+            let point = Vec2::new(
+                bounds.min.x + (x as f32 + 0.5) / pixels_per_unit,
+                bounds.max.y - (y as f32 + 0.5) / pixels_per_unit,
+            );
+
+            let pixel = colour_at(level, point).map_or([0; 4], |colour| colour.to_u8_array());
+            data.extend(pixel);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Draw the terrain, water, tees and cups as an SVG in world units
+pub fn level_to_svg(level: &Level) -> String {
+    let bounds = thumbnail_bounds(level);
+    let size = bounds.max - bounds.min;
+
+    // SVG's y axis points down
+    let flip = |point: Vec2| Vec2::new(point.x, -point.y);
+    let path_data = |outlines: &[&[Vec2]]| -> String {
+        let mut data = String::new();
+
+        for outline in outlines {
+            for (index, point) in outline.iter().map(|&point| flip(point)).enumerate() {
+                let command = if index == 0 { 'M' } else { 'L' };
+                let _ = write!(data, "{command}{} {} ", point.x, point.y);
+            }
+            data.push('Z');
+        }
+
+        data
+    };
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        bounds.min.x, -bounds.max.y, size.x, size.y
+    );
+    let _ = writeln!(svg, "    <title>{}</title>", escape_xml(&level.name));
+
+    // Holes are cut out of the outline by the even-odd rule
+    let terrain: Vec<&[Vec2]> = std::iter::once(level.points.as_slice())
+        .chain(level.holes.iter().map(Vec::as_slice))
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"    <path fill="{}" fill-rule="evenodd" d="{}" />"#,
+        level.terrain.colour.to_hex(),
+        path_data(&terrain)
+    );
+
+    for island in &level.islands {
+        let _ = writeln!(
+            svg,
+            r#"    <path fill="{}" d="{}" />"#,
+            level.terrain.colour.to_hex(),
+            path_data(&[island.as_slice()])
+        );
+    }
+
+    for pool in &level.water {
+        let _ = writeln!(
+            svg,
+            r#"    <path fill="{}" d="{}" />"#,
+            WATER_COLOUR.to_hex(),
+            path_data(&[pool.as_slice()])
+        );
+    }
+
+    // The level's own cup and regular tee keep the ids the SVG importer looks for
+    for (index, level_cup) in level.cups.iter().enumerate() {
+        let id = if index == 0 {
+            "cup".to_string()
+        } else {
+            format!("cup-{index}")
+        };
+        let cup_top_left = flip(level_cup.bottom_left + Vec2::Y * level_cup.cup.depth);
+        let _ = writeln!(
+            svg,
+            r#"    <rect id="{id}" fill="{}" x="{}" y="{}" width="{}" height="{}" />"#,
+            MARKER_COLOUR.to_hex(),
+            cup_top_left.x,
+            cup_top_left.y,
+            level_cup.cup.width,
+            level_cup.cup.depth
+        );
+    }
+
+    for (index, level_tee) in level.tees.iter().enumerate() {
+        let id = if index == 0 {
+            "tee".to_string()
+        } else {
+            format!("tee-{index}")
+        };
+        let tee = flip(level_tee.position);
+        let _ = writeln!(
+            svg,
+            r#"    <circle id="{id}" fill="{}" cx="{}" cy="{}" r="{BALL_RADIUS}" />"#,
+            MARKER_COLOUR.to_hex(),
+            tee.x,
+            tee.y
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
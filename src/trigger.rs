@@ -0,0 +1,349 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{ROSE_400, ROSE_500, STONE_400},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    ball::Ball,
+    direction::Direction,
+    level::{LevelElement, LevelState},
+    level_data::{polygon_collider, CurrentLevel},
+    obstacle::Obstacle,
+    physics::gravity_towards,
+    surface::Surface,
+    zone::ZoneShape,
+};
+
+pub struct TriggerPlugin;
+
+impl Plugin for TriggerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TriggerFiredEvent>();
+
+        app.add_systems(
+            Update,
+            (
+                fire_triggers.run_if(in_state(LevelState::InPlay)),
+                (open_gates, start_obstacles, toggle_gravity, play_sounds),
+            )
+                .chain(),
+        );
+    }
+}
+
+pub const GATE_COLOUR: Srgba = STONE_400;
+/// See-through so the ground under a sensor still shows
+const SENSOR_ALPHA: f32 = 0.3;
+
+/// A gate as it's written in a level file, it's a solid wall until a trigger opens it
+#[derive(Deserialize)]
+pub struct RawGate {
+    /// Outline of the gate, starting from the same origin as the level's outline
+    pub directions: Vec<Direction>,
+}
+
+pub struct LevelGate {
+    pub points: Vec<Vec2>,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+}
+
+/// How the ball sets a trigger off
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerKind {
+    /// An area the ball passes through
+    #[default]
+    Sensor,
+    /// Solid, the ball has to hit it
+    Button,
+}
+
+impl TriggerKind {
+    pub fn colour(&self) -> Color {
+        match self {
+            TriggerKind::Sensor => ROSE_400.with_alpha(SENSOR_ALPHA).into(),
+            TriggerKind::Button => ROSE_500.into(),
+        }
+    }
+}
+
+/// Something a trigger does when it goes off, as it's written in a level file
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum RawAction {
+    /// Index into the level's `gates`
+    OpenGate { gate: usize },
+    /// Index into the level's `obstacles`, it has to be `waiting` for this to make a difference
+    StartObstacle { obstacle: usize },
+    /// Switch between the level's own gravity and this, set the same way as the level's physics
+    ToggleGravity {
+        angle: f32,
+        #[serde(default = "default_gravity_scale")]
+        scale: f32,
+    },
+    /// `path` is from the assets folder
+    PlaySound { path: String },
+}
+
+fn default_gravity_scale() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone)]
+pub enum LevelAction {
+    OpenGate(usize),
+    StartObstacle(usize),
+    ToggleGravity(Vec2),
+    PlaySound(Handle<AudioSource>),
+}
+
+impl RawAction {
+    /// Anything the action needs loading is loaded with `load_sound`
+    pub fn resolve(self, load_sound: impl FnOnce(String) -> Handle<AudioSource>) -> LevelAction {
+        match self {
+            RawAction::OpenGate { gate } => LevelAction::OpenGate(gate),
+            RawAction::StartObstacle { obstacle } => LevelAction::StartObstacle(obstacle),
+            RawAction::ToggleGravity { angle, scale } => {
+                LevelAction::ToggleGravity(gravity_towards(angle, scale).0)
+            }
+            RawAction::PlaySound { path } => LevelAction::PlaySound(load_sound(path)),
+        }
+    }
+}
+
+/// A trigger as it's written in a level file
+#[derive(Deserialize)]
+pub struct RawTrigger {
+    pub shape: ZoneShape,
+    #[serde(default)]
+    pub kind: TriggerKind,
+    /// Only goes off the first time the ball touches it
+    #[serde(default)]
+    pub once: bool,
+    pub actions: Vec<RawAction>,
+}
+
+pub struct LevelTrigger {
+    pub points: Vec<Vec2>,
+    pub kind: TriggerKind,
+    pub once: bool,
+    pub actions: Vec<LevelAction>,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<ColorMaterial>,
+}
+
+#[derive(Component)]
+pub struct Gate {
+    /// Index into the level's `gates`
+    pub index: usize,
+    pub points: Vec<Vec2>,
+}
+
+/// A gate a trigger has opened, it stays open until the level is loaded again
+#[derive(Component)]
+pub struct Open;
+
+#[derive(Component)]
+pub struct ActionTrigger {
+    pub once: bool,
+}
+
+/// A trigger that only goes off once and already has
+#[derive(Component)]
+pub struct Spent;
+
+/// Sent with the trigger the ball set off. Each of its actions is a child entity with a component saying what it
+/// does, a new kind of action is a new component and a system that reads these.
+#[derive(Event)]
+pub struct TriggerFiredEvent(pub Entity);
+
+#[derive(Component)]
+pub struct OpenGate(pub usize);
+
+#[derive(Component)]
+pub struct StartObstacle(pub usize);
+
+#[derive(Component)]
+pub struct ToggleGravity(pub Vec2);
+
+#[derive(Component)]
+pub struct PlaySound(pub Handle<AudioSource>);
+
+/// Spawned as a child of the floor so it's part of the level's static body until it opens
+pub fn gate_bundle(gate: &LevelGate, index: usize) -> impl Bundle {
+    (
+        Name::new(format!("Gate {index}")),
+        LevelElement,
+        Gate {
+            index,
+            points: gate.points.clone(),
+        },
+        Mesh2d(gate.mesh.clone()),
+        MeshMaterial2d(gate.material.clone()),
+        polygon_collider(&gate.points).unwrap_or_default(),
+        Transform::default(),
+    )
+}
+
+/// Spawned as a child of the floor, with its actions as children of its own
+pub fn spawn_trigger(parent: &mut ChildBuilder, trigger: &LevelTrigger, index: usize) {
+    let mut entity = parent.spawn((
+        Name::new(format!("Trigger {index}")),
+        LevelElement,
+        ActionTrigger { once: trigger.once },
+        Mesh2d(trigger.mesh.clone()),
+        MeshMaterial2d(trigger.material.clone()),
+        polygon_collider(&trigger.points).unwrap_or_default(),
+        // In front of the terrain and its overlay
+        Transform::from_xyz(0.0, 0.0, 0.02),
+    ));
+
+    match trigger.kind {
+        TriggerKind::Sensor => {
+            entity.insert(Sensor);
+        }
+        TriggerKind::Button => {
+            entity.insert((Surface::Fairway.friction(), Surface::Fairway.restitution()));
+        }
+    }
+
+    entity.with_children(|actions| {
+        for action in &trigger.actions {
+            match action {
+                LevelAction::OpenGate(gate) => actions.spawn(OpenGate(*gate)),
+                LevelAction::StartObstacle(obstacle) => actions.spawn(StartObstacle(*obstacle)),
+                LevelAction::ToggleGravity(gravity) => actions.spawn(ToggleGravity(*gravity)),
+                LevelAction::PlaySound(sound) => actions.spawn(PlaySound(sound.clone())),
+            };
+        }
+    });
+}
+
+fn fire_triggers(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionStarted>,
+    ball_q: Query<Entity, With<Ball>>,
+    trigger_q: Query<&ActionTrigger, Without<Spent>>,
+    mut events: EventWriter<TriggerFiredEvent>,
+) {
+    let Ok(ball_entity) = ball_q.get_single() else {
+        return;
+    };
+
+    for CollisionStarted(entity1, entity2) in collision_events.read() {
+        let other_entity = if *entity1 == ball_entity {
+            *entity2
+        } else if *entity2 == ball_entity {
+            *entity1
+        } else {
+            continue;
+        };
+
+        let Ok(trigger) = trigger_q.get(other_entity) else {
+            continue;
+        };
+
+        if trigger.once {
+            commands.entity(other_entity).insert(Spent);
+        }
+
+        events.send(TriggerFiredEvent(other_entity));
+    }
+}
+
+/// Close every gate and let every trigger go off again, as they are when the level's loaded
+pub fn reset_triggers(
+    mut commands: Commands,
+    gate_q: Query<(Entity, &Gate), With<Open>>,
+    spent_q: Query<Entity, With<Spent>>,
+) {
+    for (entity, gate) in &gate_q {
+        commands.entity(entity).remove::<Open>().insert((
+            polygon_collider(&gate.points).unwrap_or_default(),
+            Visibility::Inherited,
+        ));
+    }
+
+    for entity in &spent_q {
+        commands.entity(entity).remove::<Spent>();
+    }
+}
+
+/// The actions of one kind belonging to triggers that have just gone off
+fn fired_actions<'a, A: Component>(
+    events: &mut EventReader<TriggerFiredEvent>,
+    action_q: &'a Query<(&Parent, &A)>,
+) -> Vec<&'a A> {
+    let fired: Vec<Entity> = events.read().map(|event| event.0).collect();
+
+    action_q
+        .iter()
+        .filter(|(parent, _)| fired.contains(&parent.get()))
+        .map(|(_, action)| action)
+        .collect()
+}
+
+fn open_gates(
+    mut commands: Commands,
+    mut events: EventReader<TriggerFiredEvent>,
+    action_q: Query<(&Parent, &OpenGate)>,
+    gate_q: Query<(Entity, &Gate), Without<Open>>,
+) {
+    for OpenGate(index) in fired_actions(&mut events, &action_q) {
+        for (entity, gate) in &gate_q {
+            if gate.index == *index {
+                commands
+                    .entity(entity)
+                    .insert((Open, Visibility::Hidden))
+                    .remove::<Collider>();
+            }
+        }
+    }
+}
+
+fn start_obstacles(
+    mut events: EventReader<TriggerFiredEvent>,
+    action_q: Query<(&Parent, &StartObstacle)>,
+    mut obstacle_q: Query<&mut Obstacle>,
+) {
+    for StartObstacle(index) in fired_actions(&mut events, &action_q) {
+        for mut obstacle in &mut obstacle_q {
+            if obstacle.index == *index {
+                obstacle.running = true;
+            }
+        }
+    }
+}
+
+/// Loading a level always puts its own gravity back
+fn toggle_gravity(
+    mut events: EventReader<TriggerFiredEvent>,
+    action_q: Query<(&Parent, &ToggleGravity)>,
+    current_level: CurrentLevel,
+    mut gravity: ResMut<Gravity>,
+) {
+    let Some(level) = current_level.get() else {
+        return;
+    };
+
+    for ToggleGravity(toggled) in fired_actions(&mut events, &action_q) {
+        gravity.0 = if gravity.0 == *toggled {
+            level.physics.gravity().0
+        } else {
+            *toggled
+        };
+    }
+}
+
+fn play_sounds(
+    mut commands: Commands,
+    mut events: EventReader<TriggerFiredEvent>,
+    action_q: Query<(&Parent, &PlaySound)>,
+) {
+    for PlaySound(sound) in fired_actions(&mut events, &action_q) {
+        commands.spawn((AudioPlayer(sound.clone()), PlaybackSettings::DESPAWN));
+    }
+}
@@ -0,0 +1,123 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    ball::{Ball, BallResetEvent, BallStoppedEvent},
+    level::LevelState,
+};
+
+pub struct WaterPlugin;
+
+impl Plugin for WaterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BallInWaterEvent>()
+            .init_resource::<LastRestingPosition>();
+
+        app.add_systems(
+            Update,
+            (
+                detect_ball_in_water.run_if(in_state(LevelState::InPlay)),
+                drop_ball_at_last_rest,
+                forget_resting_position,
+            )
+                .chain(),
+        );
+
+        app.add_systems(Last, remember_resting_position);
+    }
+}
+
+#[derive(Component)]
+pub struct Water;
+
+#[derive(Event)]
+pub struct BallInWaterEvent;
+
+/// Where the ball last came to a stop, `None` means it's on the tee
+#[derive(Resource, Default, Deref, DerefMut)]
+struct LastRestingPosition(Option<Vec2>);
+
+fn detect_ball_in_water(
+    mut collision_events: EventReader<CollisionStarted>,
+    ball_q: Query<Entity, With<Ball>>,
+    water_q: Query<(), With<Water>>,
+    mut events: EventWriter<BallInWaterEvent>,
+) {
+    let Ok(ball_entity) = ball_q.get_single() else {
+        return;
+    };
+
+    let mut is_in_water = false;
+
+    for CollisionStarted(entity1, entity2) in collision_events.read() {
+        let other_entity = if *entity1 == ball_entity {
+            *entity2
+        } else if *entity2 == ball_entity {
+            *entity1
+        } else {
+            continue;
+        };
+
+        is_in_water |= water_q.contains(other_entity);
+    }
+
+    if is_in_water {
+        events.send(BallInWaterEvent);
+    }
+}
+
+fn drop_ball_at_last_rest(
+    mut events: EventReader<BallInWaterEvent>,
+    last_resting_position: Res<LastRestingPosition>,
+    mut ball_q: Query<
+        (
+            &mut Position,
+            &mut Rotation,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        With<Ball>,
+    >,
+    mut reset_ball_events: EventWriter<BallResetEvent>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let Some(resting_position) = **last_resting_position else {
+        reset_ball_events.send(BallResetEvent);
+        return;
+    };
+
+    let Ok((mut pos, mut rot, mut vel, mut roll)) = ball_q.get_single_mut() else {
+        return;
+    };
+
+    pos.0 = resting_position;
+    *rot = Rotation::IDENTITY;
+    vel.0 = Vec2::ZERO;
+    roll.0 = 0.0;
+}
+
+fn forget_resting_position(
+    mut events: EventReader<BallResetEvent>,
+    mut last_resting_position: ResMut<LastRestingPosition>,
+) {
+    if events.read().count() > 0 {
+        **last_resting_position = None;
+    }
+}
+
+fn remember_resting_position(
+    mut events: EventReader<BallStoppedEvent>,
+    ball_q: Query<&Position, With<Ball>>,
+    mut last_resting_position: ResMut<LastRestingPosition>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    if let Ok(pos) = ball_q.get_single() {
+        **last_resting_position = Some(pos.0);
+    }
+}
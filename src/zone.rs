@@ -0,0 +1,182 @@
+use std::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::tailwind::{SKY_300, VIOLET_400},
+    math::vec2,
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    ball::Ball,
+    direction::{directions_to_points, Direction},
+    level::LevelElement,
+    level_data::{polygon_collider, LevelZone},
+    physics::PhysicsState,
+};
+
+pub struct ZonePlugin;
+
+impl Plugin for ZonePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            push_ball_in_zones.run_if(in_state(PhysicsState::Running)),
+        );
+        app.add_systems(Update, show_zone_strength);
+    }
+}
+
+/// How see-through a zone is at its usual strength
+pub const ZONE_ALPHA: f32 = 0.15;
+
+/// Where a zone is, both kinds are from the same origin as the level's outline
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneShape {
+    Rectangle {
+        /// Bottom left corner
+        position: Vec2,
+        size: Vec2,
+    },
+    Polygon(Vec<Direction>),
+}
+
+/// What a zone does to the ball while it's inside
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneEffect {
+    /// Pushes the ball the same way everywhere in the zone, like a wind tunnel or an updraft
+    Wind {
+        acceleration: Vec2,
+        #[serde(default)]
+        gust: Option<Gust>,
+    },
+    /// Changes how strong gravity is, 0 makes the ball float and 0.5 halves it
+    Gravity { scale: f32 },
+}
+
+/// Wind that rises and falls, repeating every `period` seconds. `phase` is how far through a period it starts, from 0 to 1
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Gust {
+    pub period: f32,
+    #[serde(default)]
+    pub phase: f32,
+    /// How far the wind swings either side of its acceleration, 1 means it drops to nothing then doubles
+    pub variation: f32,
+}
+
+impl ZoneEffect {
+    /// How strong the effect is compared to usual, `elapsed` seconds after the level started
+    pub fn strength(&self, elapsed: f32) -> f32 {
+        match self {
+            ZoneEffect::Wind {
+                gust: Some(gust), ..
+            } => 1.0 + gust.variation * (TAU * (elapsed / gust.period + gust.phase)).sin(),
+            _ => 1.0,
+        }
+    }
+
+    /// Acceleration on top of gravity for a ball inside the zone. This is all a shot preview needs to follow the
+    /// ball through the zone.
+    pub fn acceleration(&self, elapsed: f32, gravity: Vec2) -> Vec2 {
+        match *self {
+            ZoneEffect::Wind { acceleration, .. } => acceleration * self.strength(elapsed),
+            ZoneEffect::Gravity { scale } => gravity * (scale - 1.0),
+        }
+    }
+
+    pub fn is_gusting(&self) -> bool {
+        matches!(self, ZoneEffect::Wind { gust: Some(_), .. })
+    }
+
+    pub fn colour(&self) -> Srgba {
+        match self {
+            ZoneEffect::Wind { .. } => SKY_300,
+            ZoneEffect::Gravity { .. } => VIOLET_400,
+        }
+    }
+}
+
+impl ZoneShape {
+    /// Outline of the zone, before it's moved with the rest of the level
+    pub fn points(&self, curve_resolution: u32) -> Vec<Vec2> {
+        match self {
+            ZoneShape::Rectangle { position, size } => vec![
+                *position,
+                *position + vec2(size.x, 0.0),
+                *position + *size,
+                *position + vec2(0.0, size.y),
+                *position,
+            ],
+            ZoneShape::Polygon(directions) => directions_to_points(directions, curve_resolution).0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ForceZone {
+    pub effect: ZoneEffect,
+    /// Seconds of physics time since the zone was spawned
+    pub elapsed: f32,
+}
+
+/// Spawned as a child of the floor, the ball passes straight through it
+pub fn zone_bundle(zone: &LevelZone) -> impl Bundle {
+    (
+        Name::new("Zone"),
+        LevelElement,
+        ForceZone {
+            effect: zone.effect,
+            elapsed: 0.0,
+        },
+        Mesh2d(zone.mesh.clone()),
+        MeshMaterial2d(zone.material.clone()),
+        polygon_collider(&zone.points).unwrap_or_default(),
+        Sensor,
+        CollidingEntities::default(),
+        // Behind the terrain's overlay so water and surfaces still show through
+        Transform::from_xyz(0.0, 0.0, 0.005),
+    )
+}
+
+fn push_ball_in_zones(
+    mut ball_q: Query<(Entity, &mut LinearVelocity), With<Ball>>,
+    mut zone_q: Query<(&mut ForceZone, &CollidingEntities)>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    let Ok((ball_entity, mut vel)) = ball_q.get_single_mut() else {
+        return;
+    };
+
+    for (mut zone, colliding_entities) in &mut zone_q {
+        zone.elapsed += dt;
+
+        if colliding_entities.contains(&ball_entity) {
+            vel.0 += zone.effect.acceleration(zone.elapsed, gravity.0) * dt;
+        }
+    }
+}
+
+/// Gusting zones fade in and out with the wind
+fn show_zone_strength(
+    zone_q: Query<(&ForceZone, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (zone, material) in &zone_q {
+        if !zone.effect.is_gusting() {
+            continue;
+        }
+
+        let Some(material) = materials.get_mut(&material.0) else {
+            continue;
+        };
+
+        let alpha = ZONE_ALPHA * zone.effect.strength(zone.elapsed).clamp(0.0, 2.0);
+        material.color = zone.effect.colour().with_alpha(alpha).into();
+    }
+}